@@ -1,12 +1,15 @@
 //! Config example
 //!
 
-use std::{fs, io, path::PathBuf};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 use schemars::{JsonSchema, generate::SchemaSettings};
 use serde::{Deserialize, Serialize};
 use ts_rust_helper::{
-    command::{Cli, Command},
+    command::{Cli, Command, config_command::ConfigRegistry},
     config::{ConfigFile, try_load_config},
     error::{IntoErrorReport, ReportProgramExit},
 };
@@ -55,12 +58,12 @@ impl ConfigFile for Config {
     }
 
     fn delete(&self) -> io::Result<()> {
-        fs::remove_file(PathBuf::from("./examples/config.json"))
+        fs::remove_file(Self::config_file_path())
     }
 
-    fn write(&self) -> io::Result<()> {
+    fn write_to(&self, path: &Path) -> io::Result<()> {
         let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
-        fs::write(PathBuf::from("./config.json"), &json)
+        fs::write(path, &json)
     }
 }
 
@@ -68,7 +71,9 @@ fn main() -> ReportProgramExit {
     let cli = Cli::parse();
     if let Some(subcommand) = cli.subcommand {
         match subcommand {
-            Command::Config(config_subcommand) => config_subcommand.execute::<Config>()?,
+            Command::Config(args) => ConfigRegistry::new()
+                .register::<Config>("config")
+                .execute(&args, cli.quiet)?,
         }
 
         return Ok(());
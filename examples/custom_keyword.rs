@@ -0,0 +1,91 @@
+//! Custom keyword example
+//!
+
+use std::{collections::HashSet, path::PathBuf};
+
+use jsonschema::{
+    Keyword, ValidationError, ValidationOptions,
+    paths::{LazyLocation, Location},
+};
+use serde_json::{Value, json};
+use ts_rust_helper::json::{PositionedJsonNode, ValidationErrors, validate};
+
+const DOCUMENT: &str = r#"{
+  "tags": ["alpha", "beta", "Alpha"]
+}"#;
+
+/// Requires the string items of an array to be unique, ignoring case.
+struct UniqueCaseInsensitive {
+    schema_path: Location,
+}
+
+impl Keyword for UniqueCaseInsensitive {
+    fn validate<'i>(
+        &self,
+        instance: &'i Value,
+        location: &LazyLocation,
+    ) -> Result<(), ValidationError<'i>> {
+        if self.is_valid(instance) {
+            return Ok(());
+        }
+
+        Err(ValidationError::custom(
+            self.schema_path.clone(),
+            location.into(),
+            instance,
+            "This should not contain duplicate items, ignoring case.",
+        ))
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        let Some(items) = instance.as_array() else {
+            return true;
+        };
+
+        let mut seen = HashSet::new();
+        items
+            .iter()
+            .filter_map(Value::as_str)
+            .all(|item| seen.insert(item.to_lowercase()))
+    }
+}
+
+fn main() {
+    println!("{}", validate_tags());
+}
+
+/// Validate the document against a schema using the `x-unique-ci` keyword, the tags contain
+/// duplicates so this returns the problems.
+///
+/// This is shared with the custom keyword tests.
+pub fn validate_tags() -> ValidationErrors {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "tags": {
+                "description": "The tags for the item.",
+                "type": "array",
+                "items": { "type": "string" },
+                "x-unique-ci": true
+            }
+        }
+    });
+
+    // The keyword factory's signature is defined by `jsonschema`.
+    #[allow(clippy::result_large_err)]
+    let options = ValidationOptions::default().with_keyword("x-unique-ci", |_, _, schema_path| {
+        Ok(Box::new(UniqueCaseInsensitive { schema_path }))
+    });
+
+    let instance: Value = serde_json::from_str(DOCUMENT).unwrap();
+    let document = PositionedJsonNode::try_parse(DOCUMENT);
+
+    validate(
+        &schema,
+        &instance,
+        options,
+        document.as_ref(),
+        Some(PathBuf::from("tags.json")),
+    )
+    .expect_err("the tags contain duplicates")
+}
@@ -1,40 +1,386 @@
 //! Subcommands for working with config.
 
-use core::{error::Error, fmt};
-use std::{fs, io};
+use core::{error::Error, fmt, marker::PhantomData};
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+};
 
-use clap::Subcommand;
+use clap::{Args, Subcommand, ValueEnum};
+use jsonschema::{Draft, ValidationOptions};
+use serde_json::Value;
 
-use crate::config::{ConfigFile, LoadConfigError, try_load_config};
+use crate::{
+    config::{
+        ConfigFile, ConfigStorage, FileStorage, LoadConfigError, LoadOptions, STDIN_FILE_NAME,
+    },
+    json::{self, LintReport, SchemaError},
+    style::{BOLD, DIM, RESET, colours},
+};
+
+/// Arguments for the config subcommand.
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    /// The name of the config, required if the application has multiple configs.
+    pub name: Option<String>,
+
+    /// The subcommand.
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+/// A type erased config file that subcommands can be dispatched to.
+pub trait DynConfigFile {
+    /// Execute the subcommand for this config file, see [`ConfigSubcommand::execute`].
+    fn execute(&self, subcommand: &ConfigSubcommand, quiet: bool) -> Result<(), ExecuteError>;
+}
+
+struct RegisteredConfig<C>(PhantomData<fn() -> C>);
+impl<C: ConfigFile> DynConfigFile for RegisteredConfig<C> {
+    fn execute(&self, subcommand: &ConfigSubcommand, quiet: bool) -> Result<(), ExecuteError> {
+        subcommand.execute::<C>(quiet)
+    }
+}
+
+/// A set of named config files for an application with multiple configs.
+#[derive(Default)]
+pub struct ConfigRegistry {
+    configs: Vec<(String, Box<dyn DynConfigFile>)>,
+}
+impl ConfigRegistry {
+    /// Create a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a config file under a name.
+    pub fn register<C: ConfigFile + 'static>(mut self, name: impl ToString) -> Self {
+        self.configs.push((
+            name.to_string(),
+            Box::new(RegisteredConfig::<C>(PhantomData)),
+        ));
+        self
+    }
+
+    /// The names of the registered config files.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.configs.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Execute the subcommand for the config file selected by the arguments.
+    ///
+    /// The name may be omitted if only one config file is registered. If `quiet` is set, output
+    /// reporting success is suppressed, see [`ConfigSubcommand::execute`].
+    pub fn execute(&self, args: &ConfigArgs, quiet: bool) -> Result<(), ExecuteError> {
+        let config = match args.name.as_deref() {
+            Some(name) => self
+                .configs
+                .iter()
+                .find(|(config_name, _)| config_name == name)
+                .map(|(_, config)| config),
+            None if self.configs.len() == 1 => self.configs.first().map(|(_, config)| config),
+            None => None,
+        };
+
+        let Some(config) = config else {
+            return Err(ExecuteError::UnknownConfig {
+                name: args.name.clone(),
+                valid: self.names().map(str::to_string).collect(),
+            });
+        };
+
+        config.execute(&args.subcommand, quiet)
+    }
+}
+impl fmt::Debug for ConfigRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigRegistry")
+            .field("configs", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
 
 /// Subcommands for application config.
 #[derive(Debug, Subcommand)]
 pub enum ConfigSubcommand {
     /// Initialise the config if one does not exist.
-    Init,
+    Init {
+        /// Print what would be written without writing it.
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Reset all configs.
-    Reset,
+    Reset {
+        /// Print what would be deleted and written without changing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Output the config JSON schema
-    Schema,
-    /// Lint the config
-    Lint,
+    Schema {
+        /// Convert the schema to the draft, warning about keywords that can't be translated.
+        #[arg(long, value_enum, value_name = "VERSION")]
+        draft: Option<SchemaDraft>,
+    },
+    /// Output an example config built from the schema
+    Example,
+    /// Fix the problems in the config that have a suggested fix, such as unexpected properties
+    Fix {
+        /// The config file to fix instead of the config file.
+        path: Option<PathBuf>,
+
+        /// Write the fixes even if the config still has errors after fixing.
+        #[arg(long)]
+        partial: bool,
+    },
+    /// Lint the config, exiting with an error only if a config has error level problems, or any
+    /// problems with `--strict`
+    Lint {
+        /// Config files, or directories of config files, to lint instead of the config file. A
+        /// path of `-` reads the config from stdin.
+        paths: Vec<PathBuf>,
+
+        /// Suppress problems with the code, such as `additional-properties`.
+        #[arg(long, value_name = "CODE")]
+        suppress: Vec<String>,
+
+        /// A JSON schema file to validate against instead of the config's schema, repeat to
+        /// require the config to satisfy every schema.
+        #[arg(long, value_name = "PATH")]
+        schema: Vec<PathBuf>,
+
+        /// Fail if a config has any problems, including warnings.
+        #[arg(long)]
+        strict: bool,
+
+        /// Merge problems that differ only by an array item's index into one problem, listing
+        /// the items it applies to.
+        #[arg(long)]
+        group_items: bool,
+
+        /// The format to output the problems in.
+        #[arg(long, value_enum, default_value_t)]
+        format: LintFormat,
+    },
+    /// Validate the config against the schema without deserializing it, exiting with an error
+    /// only if a config has error level problems, or any problems with `--strict`
+    Check {
+        /// Config files, or directories of config files, to check instead of the config file. A
+        /// path of `-` reads the config from stdin.
+        paths: Vec<PathBuf>,
+
+        /// Suppress problems with the code, such as `additional-properties`.
+        #[arg(long, value_name = "CODE")]
+        suppress: Vec<String>,
+
+        /// A JSON schema file to validate against instead of the config's schema, repeat to
+        /// require the config to satisfy every schema.
+        #[arg(long, value_name = "PATH")]
+        schema: Vec<PathBuf>,
+
+        /// Fail if a config has any problems, including warnings.
+        #[arg(long)]
+        strict: bool,
+
+        /// Merge problems that differ only by an array item's index into one problem, listing
+        /// the items it applies to.
+        #[arg(long)]
+        group_items: bool,
+
+        /// The format to output the problems in.
+        #[arg(long, value_enum, default_value_t)]
+        format: LintFormat,
+    },
+}
+
+/// The output formats for linting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[non_exhaustive]
+pub enum LintFormat {
+    /// Rendered problems and a summary.
+    #[default]
+    Human,
+    /// The problems for each config as JSON.
+    Json,
+    /// The problems for all configs as a SARIF log.
+    Sarif,
+}
+
+/// The JSON schema drafts the schema can be output as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[non_exhaustive]
+pub enum SchemaDraft {
+    /// Draft 4.
+    #[value(name = "4")]
+    Draft4,
+    /// Draft 6.
+    #[value(name = "6")]
+    Draft6,
+    /// Draft 7.
+    #[value(name = "7")]
+    Draft7,
+    /// Draft 2019-09.
+    #[value(name = "2019-09")]
+    Draft201909,
+    /// Draft 2020-12.
+    #[value(name = "2020-12")]
+    Draft202012,
+}
+impl From<SchemaDraft> for Draft {
+    fn from(value: SchemaDraft) -> Self {
+        match value {
+            SchemaDraft::Draft4 => Self::Draft4,
+            SchemaDraft::Draft6 => Self::Draft6,
+            SchemaDraft::Draft7 => Self::Draft7,
+            SchemaDraft::Draft201909 => Self::Draft201909,
+            SchemaDraft::Draft202012 => Self::Draft202012,
+        }
+    }
 }
 
 impl ConfigSubcommand {
     /// Execute the subcommand.
-    pub fn execute<C: ConfigFile>(&self) -> Result<(), ExecuteError> {
+    ///
+    /// If `quiet` is set, output reporting success is suppressed, such as the lint report when no
+    /// config has problems. Problems, errors, and the output that was asked for, such as the
+    /// schema, dry runs, and machine readable lint reports, are still printed.
+    pub fn execute<C: ConfigFile>(&self, quiet: bool) -> Result<(), ExecuteError> {
+        let path = C::config_file_path();
+
         match &self {
-            Self::Init => {
-                Self::init::<C>().map_err(|source| ExecuteError::Init { source })?;
+            Self::Init { dry_run } => {
+                Self::init::<C>(*dry_run).map_err(|source| ExecuteError::Init { source })?;
+
+                if !quiet && !dry_run {
+                    println!(
+                        "{BOLD}{}initialised{RESET} `{}`",
+                        colours().success,
+                        path.to_string_lossy()
+                    );
+                }
+            }
+            Self::Reset { dry_run } => {
+                Self::reset::<C>(*dry_run).map_err(|source| ExecuteError::Reset { source })?;
+
+                if !quiet && !dry_run {
+                    println!(
+                        "{BOLD}{}reset{RESET} `{}`",
+                        colours().success,
+                        path.to_string_lossy()
+                    );
+                }
+            }
+            Self::Schema { draft } => {
+                match draft {
+                    Some(draft) => Self::schema_as_draft::<C>((*draft).into()),
+                    None => Self::schema::<C>(),
+                }
+                .map_err(|source| ExecuteError::Schema { source })?;
             }
-            Self::Reset => {
-                Self::reset::<C>().map_err(|source| ExecuteError::Reset { source })?;
+            Self::Example => {
+                Self::example::<C>().map_err(|source| ExecuteError::Example { source })?;
             }
-            Self::Schema => {
-                Self::schema::<C>().map_err(|source| ExecuteError::Schema { source })?;
+            Self::Fix {
+                path: fix_path,
+                partial,
+            } => {
+                let path = fix_path.as_ref().unwrap_or(&path);
+                let fixed = Self::fix::<C>(path, &LoadOptions::default(), *partial)
+                    .map_err(|source| ExecuteError::Fix { source })?;
+
+                for message in &fixed {
+                    println!("{BOLD}{}fixed{RESET} {message}", colours().success);
+                }
+                if !quiet {
+                    if fixed.is_empty() {
+                        println!(
+                            "{BOLD}{}ok{RESET} nothing to fix in `{}`",
+                            colours().success,
+                            path.to_string_lossy()
+                        );
+                    } else {
+                        println!(
+                            "{BOLD}{}ok{RESET} applied {} fixes to `{}`",
+                            colours().success,
+                            fixed.len(),
+                            path.to_string_lossy()
+                        );
+                    }
+                }
             }
-            Self::Lint => {
-                Self::lint::<C>().map_err(|source| ExecuteError::Lint { source })?;
+            Self::Lint {
+                paths,
+                suppress,
+                schema,
+                strict,
+                group_items,
+                format,
+            }
+            | Self::Check {
+                paths,
+                suppress,
+                schema,
+                strict,
+                group_items,
+                format,
+            } => {
+                let deserialize = matches!(self, Self::Lint { .. });
+
+                let options = LoadOptions {
+                    suppress: suppress.clone(),
+                    strict: *strict,
+                    group_by_index: *group_items,
+                    schemas: schema
+                        .iter()
+                        .map(|path| load_schema(path, C::validation_options()))
+                        .collect::<Result<_, _>>()?,
+                };
+
+                if deserialize && paths.is_empty() && *format == LintFormat::Human {
+                    let _ = Self::lint::<C>(&options)
+                        .map_err(|source| ExecuteError::Lint { source })?;
+
+                    if !quiet {
+                        println!(
+                            "{BOLD}{}ok{RESET} `{}`",
+                            colours().success,
+                            path.to_string_lossy()
+                        );
+                    }
+                    return Ok(());
+                }
+
+                let paths = if paths.is_empty() {
+                    &[path][..]
+                } else {
+                    paths.as_slice()
+                };
+                let report = Self::lint_each::<C>(paths, &options, deserialize)?;
+                match format {
+                    LintFormat::Human => {
+                        if !quiet || !report.is_empty() {
+                            println!("{report}");
+                        }
+                    }
+                    LintFormat::Json => {
+                        let json = serde_json::to_string_pretty(&report)
+                            .map_err(|source| ExecuteError::SerializeReport { source })?;
+                        println!("{json}");
+                    }
+                    LintFormat::Sarif => {
+                        let sarif = serde_json::to_string_pretty(&report.to_sarif(&tool_name()))
+                            .map_err(|source| ExecuteError::SerializeReport { source })?;
+                        println!("{sarif}");
+                    }
+                }
+
+                let failed = report.failed();
+                if failed > 0 {
+                    return Err(ExecuteError::LintFiles {
+                        failed,
+                        checked: report.files.len(),
+                    });
+                }
             }
         };
 
@@ -42,9 +388,90 @@ impl ConfigSubcommand {
     }
 
     /// Lint the config file.
-    pub fn lint<C: ConfigFile>() -> Result<(), LoadConfigError> {
-        let _ = try_load_config::<C>()?;
-        Ok(())
+    ///
+    /// Warnings are printed but do not fail linting unless the options are strict, an error is only
+    /// returned if the config has error level problems or could not be loaded. The loaded config
+    /// is returned so it does not need to be loaded again.
+    pub fn lint<C: ConfigFile>(options: &LoadOptions) -> Result<C, LoadConfigError> {
+        let loaded = options.load::<C>()?;
+        if !loaded.problems.problems.is_empty() {
+            eprintln!("{}", loaded.problems);
+        }
+
+        Ok(loaded.config)
+    }
+
+    /// Lint many config files, returning a report of the problems for each file once all files
+    /// have been checked.
+    ///
+    /// Files that could not be loaded are reported with a single problem describing why. Files
+    /// with only warnings pass unless the options are strict.
+    ///
+    /// Directories are linted by checking each `.json` file directly inside them, and a path of `-`
+    /// reads a config from stdin. When stderr is a terminal, a progress counter is shown as each
+    /// file is checked.
+    pub fn lint_files<C: ConfigFile>(
+        paths: &[PathBuf],
+        options: &LoadOptions,
+    ) -> Result<LintReport, ExecuteError> {
+        Self::lint_each::<C>(paths, options, true)
+    }
+
+    /// Check many config files against the schema without deserializing them, see
+    /// [`LoadOptions::check`] and [`ConfigSubcommand::lint_files`].
+    ///
+    /// Use this when the schema may have drifted from the config type, such as a schema from
+    /// `--schema`, as a valid config that can't be deserialized is not reported as a failure.
+    pub fn check_files<C: ConfigFile>(
+        paths: &[PathBuf],
+        options: &LoadOptions,
+    ) -> Result<LintReport, ExecuteError> {
+        Self::lint_each::<C>(paths, options, false)
+    }
+
+    /// Lint each config file, only deserializing them if `deserialize` is set.
+    fn lint_each<C: ConfigFile>(
+        paths: &[PathBuf],
+        options: &LoadOptions,
+        deserialize: bool,
+    ) -> Result<LintReport, ExecuteError> {
+        let files = collect_files(paths)?;
+        let show_progress = io::stderr().is_terminal();
+
+        let mut report = LintReport {
+            files: Vec::new(),
+            strict: options.strict,
+        };
+        for (index, file) in files.iter().enumerate() {
+            let is_stdin = file.as_os_str() == "-";
+
+            if show_progress {
+                let name = if is_stdin {
+                    STDIN_FILE_NAME.into()
+                } else {
+                    file.to_string_lossy()
+                };
+                eprintln!(
+                    "{DIM}[{}/{}]{RESET} checking `{name}`",
+                    index + 1,
+                    files.len(),
+                );
+            }
+
+            let result = match (is_stdin, deserialize) {
+                (true, true) => options
+                    .load_from_reader::<C, _>(io::stdin().lock())
+                    .map(|loaded| loaded.problems),
+                (true, false) => options.check_from_reader::<C, _>(io::stdin().lock()),
+                (false, true) => options
+                    .load_from_path::<C>(file)
+                    .map(|loaded| loaded.problems),
+                (false, false) => options.check_from_path::<C>(file),
+            };
+            report.push(result.unwrap_or_else(LoadConfigError::into_validation_errors));
+        }
+
+        Ok(report)
     }
 
     /// Output the schema
@@ -55,9 +482,106 @@ impl ConfigSubcommand {
         Ok(())
     }
 
+    /// Fix the config file at the path by applying the suggested fixes for it's problems, see
+    /// [`ValidationProblem::suggestion`](crate::json::ValidationProblem::suggestion).
+    ///
+    /// Fixes are applied in passes, validating the config again after each pass, until no more
+    /// fixes apply. The fixed config is only written if it no longer has errors, or if `partial` is
+    /// set and its remaining problems are validation problems. Returns the messages of the fixes
+    /// that were applied.
+    pub fn fix<C: ConfigFile>(
+        path: &Path,
+        options: &LoadOptions,
+        partial: bool,
+    ) -> Result<Vec<String>, FixError> {
+        const MAX_PASSES: usize = 8;
+
+        let mut source =
+            fs::read_to_string(path).map_err(|source| FixError::ReadConfig { source })?;
+
+        let mut fixed = Vec::new();
+        let mut result = options.check_source::<C>(&source, path);
+        for _ in 0..MAX_PASSES {
+            let problems = match &result {
+                Ok(problems) => problems,
+                Err(error) => match error.validation_errors() {
+                    Some(problems) => problems,
+                    None => break,
+                },
+            };
+
+            let suggestions: Vec<_> = problems
+                .problems
+                .iter()
+                .filter_map(|problem| problem.suggestion.as_ref())
+                .collect();
+            let (edited, applied) = json::apply_suggestions(&source, &suggestions);
+            if applied.is_empty() {
+                break;
+            }
+
+            fixed.extend(
+                applied
+                    .into_iter()
+                    .map(|index| suggestions[index].message.clone()),
+            );
+            source = edited;
+            result = options.check_source::<C>(&source, path);
+        }
+
+        match result {
+            Ok(_) => {}
+            Err(error) if partial && error.validation_errors().is_some() => {}
+            Err(source) => {
+                return Err(FixError::Unresolved {
+                    fixed: fixed.len(),
+                    source,
+                });
+            }
+        }
+
+        if !fixed.is_empty() {
+            FileStorage
+                .write(path, source.as_bytes())
+                .map_err(|source| FixError::WriteConfig { source })?;
+        }
+
+        Ok(fixed)
+    }
+
+    /// Output the example config, see [`ConfigFile::example`].
+    pub fn example<C: ConfigFile>() -> serde_json::Result<()> {
+        let json = serde_json::to_string_pretty(&C::example())?;
+        println!("{json}");
+
+        Ok(())
+    }
+
+    /// Output the schema converted to the draft, see [`json::convert_draft`].
+    ///
+    /// Keywords that can't be translated to the draft are warned about on stderr.
+    pub fn schema_as_draft<C: ConfigFile>(draft: Draft) -> serde_json::Result<()> {
+        let conversion = json::convert_draft(&C::schema(), draft);
+        for keyword in &conversion.untranslated {
+            eprintln!(
+                "{BOLD}{}warning{RESET}{BOLD}:{RESET} {keyword}",
+                colours().warning
+            );
+        }
+
+        let json = serde_json::to_string_pretty(&conversion.schema)?;
+        println!("{json}");
+
+        Ok(())
+    }
+
     /// Initialise the config.
-    pub fn init<C: ConfigFile>() -> Result<C, InitError> {
-        if C::config_file_path()
+    ///
+    /// If `dry_run` is set, the config that would be written is printed instead.
+    pub fn init<C: ConfigFile>(dry_run: bool) -> Result<C, InitError> {
+        let path = C::config_file_path();
+
+        if path
             .try_exists()
             .map_err(|source| InitError::CheckPathExists { source })?
         {
@@ -65,6 +589,17 @@ impl ConfigSubcommand {
         }
 
         let config = C::default();
+
+        if dry_run {
+            let json = config
+                .to_json()
+                .map_err(|source| InitError::SerializeConfig { source })?;
+            let json = String::from_utf8_lossy(&json);
+            println!("would write `{}`:\n{json}", path.to_string_lossy());
+
+            return Ok(config);
+        }
+
         config
             .write()
             .map_err(|source| InitError::WriteConfig { source })?;
@@ -73,16 +608,36 @@ impl ConfigSubcommand {
     }
 
     /// Reset the config.
-    pub fn reset<C: ConfigFile>() -> Result<C, ResetError> {
-        if C::config_file_path()
+    ///
+    /// If `dry_run` is set, the config that would be deleted and the config that would be written
+    /// are printed instead.
+    pub fn reset<C: ConfigFile>(dry_run: bool) -> Result<C, ResetError> {
+        let path = C::config_file_path();
+
+        let exists = path
             .try_exists()
-            .map_err(|source| ResetError::CheckPathExists { source })?
-        {
-            fs::remove_file(C::config_file_path())
-                .map_err(|source| ResetError::DeleteConfig { source })?;
-        }
+            .map_err(|source| ResetError::CheckPathExists { source })?;
 
         let config = C::default();
+
+        if dry_run {
+            if exists {
+                println!("would delete `{}`", path.to_string_lossy());
+            }
+
+            let json = config
+                .to_json()
+                .map_err(|source| ResetError::SerializeConfig { source })?;
+            let json = String::from_utf8_lossy(&json);
+            println!("would write `{}`:\n{json}", path.to_string_lossy());
+
+            return Ok(config);
+        }
+
+        if exists {
+            fs::remove_file(&path).map_err(|source| ResetError::DeleteConfig { source })?;
+        }
+
         config
             .write()
             .map_err(|source| ResetError::WriteConfig { source })?;
@@ -91,6 +646,70 @@ impl ConfigSubcommand {
     }
 }
 
+/// Expand the paths into the files to lint, directories are expanded into the `.json` files
+/// directly inside them.
+fn collect_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, ExecuteError> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+
+        let read_directory_error = |source| ExecuteError::ReadDirectory {
+            path: path.clone(),
+            source,
+        };
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(path).map_err(read_directory_error)? {
+            let entry_path = entry.map_err(read_directory_error)?.path();
+            if entry_path.is_file() && is_json(&entry_path) {
+                entries.push(entry_path);
+            }
+        }
+        entries.sort();
+
+        files.extend(entries);
+    }
+
+    Ok(files)
+}
+
+/// Read a JSON schema file, checking it can be used to create a validator.
+fn load_schema(path: &Path, validation_options: ValidationOptions) -> Result<Value, ExecuteError> {
+    let invalid_schema = |source| ExecuteError::InvalidSchema {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    let raw_schema = fs::read_to_string(path)
+        .map_err(|source| invalid_schema(InvalidSchemaError::Read { source }))?;
+    let schema = serde_json::from_str(&raw_schema)
+        .map_err(|source| invalid_schema(InvalidSchemaError::Parse { source }))?;
+    json::check_schema(&schema, validation_options)
+        .map_err(|source| invalid_schema(InvalidSchemaError::Compile { source }))?;
+
+    Ok(schema)
+}
+
+/// The name of the running binary, used as the tool name in reports.
+fn tool_name() -> String {
+    env::current_exe()
+        .ok()
+        .and_then(|path| {
+            path.file_stem()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "program".to_string())
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
 /// Failed to execute the subcommand.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -105,8 +724,35 @@ pub enum ExecuteError {
     #[non_exhaustive]
     Schema { source: serde_json::Error },
 
+    #[non_exhaustive]
+    Example { source: serde_json::Error },
+
+    #[non_exhaustive]
+    Fix { source: FixError },
+
     #[non_exhaustive]
     Lint { source: LoadConfigError },
+
+    #[non_exhaustive]
+    LintFiles { failed: usize, checked: usize },
+
+    #[non_exhaustive]
+    ReadDirectory { path: PathBuf, source: io::Error },
+
+    #[non_exhaustive]
+    InvalidSchema {
+        path: PathBuf,
+        source: InvalidSchemaError,
+    },
+
+    #[non_exhaustive]
+    SerializeReport { source: serde_json::Error },
+
+    #[non_exhaustive]
+    UnknownConfig {
+        name: Option<String>,
+        valid: Vec<String>,
+    },
 }
 impl fmt::Display for ExecuteError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -114,12 +760,32 @@ impl fmt::Display for ExecuteError {
             Self::Reset { .. } => write!(f, "could not reset config"),
             Self::Init { .. } => write!(f, "could not initialise config"),
             Self::Schema { .. } => write!(f, "could not output the JSON schema"),
+            Self::Example { .. } => write!(f, "could not output the example config"),
+            Self::Fix { .. } => write!(f, "could not fix config"),
             Self::Lint { source } => match source {
                 LoadConfigError::ValidationError { .. } => {
                     write!(f, "linting reported that the config contained errors")
                 }
                 _ => write!(f, "config could not be validated"),
             },
+            Self::LintFiles { failed, checked } => write!(
+                f,
+                "linting reported that {failed} of {checked} configs failed"
+            ),
+            Self::ReadDirectory { path, .. } => {
+                write!(f, "could not read directory `{}`", path.to_string_lossy())
+            }
+            Self::InvalidSchema { path, .. } => {
+                write!(f, "could not load schema `{}`", path.to_string_lossy())
+            }
+            Self::SerializeReport { .. } => write!(f, "could not serialize the lint report"),
+            Self::UnknownConfig { name, valid } => {
+                match name {
+                    Some(name) => write!(f, "unknown config `{name}`")?,
+                    None => write!(f, "a config name is required")?,
+                }
+                write!(f, ", expected one of: {}", valid.join(", "))
+            }
         }
     }
 }
@@ -129,7 +795,88 @@ impl Error for ExecuteError {
             Self::Reset { source, .. } => Some(source),
             Self::Init { source, .. } => Some(source),
             Self::Schema { source, .. } => Some(source),
+            Self::Example { source, .. } => Some(source),
+            Self::Fix { source, .. } => Some(source),
             Self::Lint { source, .. } => Some(source),
+            Self::ReadDirectory { source, .. } => Some(source),
+            Self::InvalidSchema { source, .. } => Some(source),
+            Self::SerializeReport { source, .. } => Some(source),
+            Self::LintFiles { .. } | Self::UnknownConfig { .. } => None,
+        }
+    }
+}
+
+/// Error variants for loading a schema file.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum InvalidSchemaError {
+    #[non_exhaustive]
+    Read { source: io::Error },
+
+    #[non_exhaustive]
+    Parse { source: serde_json::Error },
+
+    #[non_exhaustive]
+    Compile { source: SchemaError },
+}
+impl fmt::Display for InvalidSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Read { .. } => write!(f, "could not read the schema file"),
+            Self::Parse { .. } => write!(f, "the schema file is not valid JSON"),
+            Self::Compile { .. } => write!(f, "the schema could not create a validator"),
+        }
+    }
+}
+impl Error for InvalidSchemaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            Self::Read { source, .. } => Some(source),
+            Self::Parse { source, .. } => Some(source),
+            Self::Compile { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Error variants for fixing.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum FixError {
+    #[non_exhaustive]
+    ReadConfig { source: io::Error },
+
+    #[non_exhaustive]
+    Unresolved {
+        fixed: usize,
+        source: LoadConfigError,
+    },
+
+    #[non_exhaustive]
+    WriteConfig { source: io::Error },
+}
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::ReadConfig { .. } => write!(f, "could not read the config"),
+            Self::Unresolved { fixed: 0, .. } => {
+                write!(f, "the config has errors that can't be fixed")
+            }
+            Self::Unresolved { fixed, .. } => write!(
+                f,
+                "{fixed} fixes did not resolve every error, use `--partial` to write them anyway"
+            ),
+            Self::WriteConfig { .. } => write!(f, "could not write the fixed config"),
+        }
+    }
+}
+impl Error for FixError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            Self::ReadConfig { source, .. } => Some(source),
+            Self::Unresolved { source, .. } => Some(source),
+            Self::WriteConfig { source, .. } => Some(source),
         }
     }
 }
@@ -147,6 +894,9 @@ pub enum ResetError {
 
     #[non_exhaustive]
     DeleteConfig { source: io::Error },
+
+    #[non_exhaustive]
+    SerializeConfig { source: serde_json::Error },
 }
 impl fmt::Display for ResetError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -154,6 +904,7 @@ impl fmt::Display for ResetError {
             Self::CheckPathExists { .. } => write!(f, "could not check if the config exists"),
             Self::WriteConfig { .. } => write!(f, "could not write new config"),
             Self::DeleteConfig { .. } => write!(f, "could not delete old config"),
+            Self::SerializeConfig { .. } => write!(f, "could not serialize new config"),
         }
     }
 }
@@ -163,6 +914,7 @@ impl Error for ResetError {
             Self::CheckPathExists { source, .. } => Some(source),
             Self::WriteConfig { source, .. } => Some(source),
             Self::DeleteConfig { source, .. } => Some(source),
+            Self::SerializeConfig { source, .. } => Some(source),
         }
     }
 }
@@ -180,6 +932,9 @@ pub enum InitError {
 
     #[non_exhaustive]
     WriteConfig { source: io::Error },
+
+    #[non_exhaustive]
+    SerializeConfig { source: serde_json::Error },
 }
 impl fmt::Display for InitError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -187,6 +942,7 @@ impl fmt::Display for InitError {
             Self::CheckPathExists { .. } => write!(f, "could not check if the config exists"),
             Self::WriteConfig { .. } => write!(f, "could not write new config"),
             Self::AlreadyInitialised { .. } => write!(f, "the config is already initialised"),
+            Self::SerializeConfig { .. } => write!(f, "could not serialize new config"),
         }
     }
 }
@@ -195,6 +951,7 @@ impl Error for InitError {
         match &self {
             Self::CheckPathExists { source, .. } => Some(source),
             Self::WriteConfig { source, .. } => Some(source),
+            Self::SerializeConfig { source, .. } => Some(source),
             _ => None,
         }
     }
@@ -1,11 +1,26 @@
 //! A basic CLI for generic applications.
 
 pub mod config_command;
+mod run;
 
-use clap::{Parser, Subcommand};
+use core::fmt;
+use std::{ffi::OsString, process};
+
+use clap::{
+    CommandFactory, FromArgMatches, Parser, Subcommand,
+    builder::{Styles, styling::AnsiColor},
+};
+
+use crate::{command::config_command::ConfigSubcommand, error::program_name};
+
+pub use run::{CommandExt, RunCommandError, run_checked};
 
 /// A basic CLI.
+///
+/// `--version` is only enabled by [`Cli::parse_with_build_info`], as this crate can't know the
+/// binary's version.
 #[derive(Debug, Parser)]
+#[command(styles = styles())]
 pub struct Cli {
     /// The subcommand
     #[command(subcommand)]
@@ -14,14 +29,21 @@ pub struct Cli {
     /// Enable verbose logging.
     #[arg(long, action)]
     pub verbose: bool,
+
+    /// Only print errors and problems, suppressing output that reports success.
+    #[arg(long, short, action, global = true)]
+    pub quiet: bool,
+
+    /// Print the build information.
+    #[arg(long, action, hide = true)]
+    pub build_info: bool,
 }
 
 /// Subcommands for the CLI.
 #[derive(Debug, Subcommand)]
 pub enum Command {
     /// Config subcommand.
-    #[command(subcommand)]
-    Config(config_command::ConfigSubcommand),
+    Config(config_command::ConfigArgs),
 }
 
 impl Cli {
@@ -29,4 +51,152 @@ impl Cli {
     pub fn parse() -> Self {
         <Self as Parser>::parse()
     }
+
+    /// Try parse the CLI arguments, returning an error instead of exiting the process.
+    ///
+    /// `--help` is returned as an error, its output is the error's message.
+    pub fn try_parse() -> Result<Self, clap::Error> {
+        <Self as Parser>::try_parse()
+    }
+
+    /// Try parse the CLI arguments from an iterator, returning an error instead of exiting the
+    /// process.
+    ///
+    /// The first item is the binary name.
+    pub fn try_parse_from<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        <Self as Parser>::try_parse_from(args)
+    }
+
+    /// The name of the parsed subcommand, such as `config init`, for logging which subcommand ran.
+    ///
+    /// Returns the binary's file name if no subcommand was given.
+    pub fn command_name(&self) -> &'static str {
+        let Some(subcommand) = self.subcommand.as_ref() else {
+            return program_name();
+        };
+
+        match subcommand {
+            Command::Config(args) => match args.subcommand {
+                ConfigSubcommand::Init { .. } => "config init",
+                ConfigSubcommand::Reset { .. } => "config reset",
+                ConfigSubcommand::Schema { .. } => "config schema",
+                ConfigSubcommand::Example => "config example",
+                ConfigSubcommand::Fix { .. } => "config fix",
+                ConfigSubcommand::Lint { .. } => "config lint",
+                ConfigSubcommand::Check { .. } => "config check",
+            },
+        }
+    }
+
+    /// Parse the CLI arguments, using the binary's build information for `--version` and enabling
+    /// `--build-info`.
+    ///
+    /// If `--build-info` is given, the build information is printed and the process exits.
+    pub fn parse_with_build_info(build_info: &BuildInfo) -> Self {
+        let matches = Self::command_with_build_info(build_info).get_matches();
+        let cli = <Self as FromArgMatches>::from_arg_matches(&matches)
+            .unwrap_or_else(|error| error.exit());
+
+        if cli.build_info {
+            println!("{build_info}");
+            process::exit(0);
+        }
+
+        cli
+    }
+
+    /// Try parse the CLI arguments from an iterator, using the binary's build information for
+    /// `--version` and enabling `--build-info`, see [`Cli::parse_with_build_info`].
+    ///
+    /// The first item is the binary name. The build information is not printed, check
+    /// [`Cli::build_info`] instead.
+    pub fn try_parse_from_with_build_info<I, T>(
+        build_info: &BuildInfo,
+        args: I,
+    ) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = Self::command_with_build_info(build_info).try_get_matches_from(args)?;
+        <Self as FromArgMatches>::from_arg_matches(&matches)
+    }
+
+    /// The command with the binary's version and `--build-info` shown.
+    fn command_with_build_info(build_info: &BuildInfo) -> clap::Command {
+        <Self as CommandFactory>::command()
+            .version(build_info.version)
+            .mut_arg("build_info", |arg| arg.hide(false))
+    }
+}
+
+/// The clap styles matching the palette used by the `style` module.
+///
+/// Without the `styled` feature this is unstyled.
+pub fn styles() -> Styles {
+    if cfg!(feature = "styled") {
+        Styles::styled()
+            .header(AnsiColor::BrightCyan.on_default().bold())
+            .usage(AnsiColor::BrightCyan.on_default().bold())
+            .literal(AnsiColor::BrightCyan.on_default())
+            .placeholder(AnsiColor::BrightCyan.on_default().dimmed())
+            .error(AnsiColor::BrightRed.on_default().bold())
+            .valid(AnsiColor::BrightGreen.on_default())
+            .invalid(AnsiColor::BrightYellow.on_default())
+    } else {
+        Styles::plain()
+    }
+}
+
+/// Build information for a binary.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct BuildInfo {
+    /// The binary's version.
+    pub version: &'static str,
+    /// The git SHA the binary was built from.
+    pub git_sha: Option<&'static str>,
+    /// The rustc version the binary was built with.
+    pub rustc_version: Option<&'static str>,
+}
+impl BuildInfo {
+    /// Create new build information, usually `BuildInfo::new(env!("CARGO_PKG_VERSION"))`.
+    pub const fn new(version: &'static str) -> Self {
+        Self {
+            version,
+            git_sha: None,
+            rustc_version: None,
+        }
+    }
+
+    /// Set the git SHA, usually from an env set by the binary's build script such as
+    /// `option_env!("GIT_SHA")`.
+    pub const fn with_git_sha(mut self, git_sha: Option<&'static str>) -> Self {
+        self.git_sha = git_sha;
+        self
+    }
+
+    /// Set the rustc version, usually from an env set by the binary's build script such as
+    /// `option_env!("RUSTC_VERSION")`.
+    pub const fn with_rustc_version(mut self, rustc_version: Option<&'static str>) -> Self {
+        self.rustc_version = rustc_version;
+        self
+    }
+}
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "version: {}", self.version)?;
+        if let Some(git_sha) = self.git_sha {
+            write!(f, "\ngit SHA: {git_sha}")?;
+        }
+        if let Some(rustc_version) = self.rustc_version {
+            write!(f, "\nrustc:   {rustc_version}")?;
+        }
+
+        Ok(())
+    }
 }
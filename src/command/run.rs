@@ -0,0 +1,117 @@
+use core::{error::Error, fmt, iter};
+use std::{
+    ffi::OsStr,
+    io,
+    process::{Command, ExitStatus, Output},
+};
+
+/// Extension trait for running external processes.
+pub trait CommandExt {
+    /// Run the command to completion, capturing stdout and stderr, see [`run_checked`].
+    fn run_checked(&mut self) -> Result<Output, RunCommandError>;
+}
+
+impl CommandExt for Command {
+    fn run_checked(&mut self) -> Result<Output, RunCommandError> {
+        run_checked(self)
+    }
+}
+
+/// Run the command to completion, capturing stdout and stderr.
+///
+/// The command failing to start or exiting unsuccessfully is an error that includes the command
+/// line and the captured stderr, so it can be reported with
+/// [`IntoErrorReport`](crate::error::IntoErrorReport).
+pub fn run_checked(command: &mut Command) -> Result<Output, RunCommandError> {
+    let output = command
+        .output()
+        .map_err(|source| RunCommandError::spawn(command, source))?;
+
+    if !output.status.success() {
+        return Err(RunCommandError::status(command, &output));
+    }
+
+    Ok(output)
+}
+
+/// Error variants for running an external process.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum RunCommandError {
+    #[non_exhaustive]
+    Spawn {
+        command_line: String,
+        source: io::Error,
+    },
+
+    #[non_exhaustive]
+    Status {
+        command_line: String,
+        status: ExitStatus,
+        stderr: String,
+    },
+}
+impl fmt::Display for RunCommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Spawn { command_line, .. } => write!(f, "could not run `{command_line}`"),
+            Self::Status {
+                command_line,
+                status,
+                stderr,
+            } => {
+                write!(f, "`{command_line}` failed with {status}")?;
+
+                let stderr = stderr.trim();
+                if !stderr.is_empty() {
+                    write!(f, "\n{stderr}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+impl Error for RunCommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            Self::Spawn { source, .. } => Some(source),
+            Self::Status { .. } => None,
+        }
+    }
+}
+impl RunCommandError {
+    #![allow(missing_docs)]
+    pub fn spawn(command: &Command, source: io::Error) -> Self {
+        Self::Spawn {
+            command_line: command_line(command),
+            source,
+        }
+    }
+    pub fn status(command: &Command, output: &Output) -> Self {
+        Self::Status {
+            command_line: command_line(command),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }
+    }
+}
+
+/// Render the command's program and arguments, quoting arguments that contain whitespace.
+fn command_line(command: &Command) -> String {
+    let quote = |argument: &OsStr| {
+        let argument = argument.to_string_lossy();
+        if argument.is_empty() || argument.contains(char::is_whitespace) {
+            format!("\"{argument}\"")
+        } else {
+            argument.into_owned()
+        }
+    };
+
+    iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
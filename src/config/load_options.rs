@@ -0,0 +1,375 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::{
+    config::{ConfigFile, ConfigStorage, FileStorage, LoadConfigError, STDIN_FILE_NAME},
+    json::{self, PositionedJsonNode, SchemaError, Severity, ValidationErrors, ValidationProblem},
+};
+
+/// Options for loading a config.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct LoadOptions {
+    /// The codes of the problems to suppress, see [`ValidationProblem::code`].
+    ///
+    /// [`ValidationProblem::code`]: crate::json::ValidationProblem::code
+    pub suppress: Vec<String>,
+    /// Fail to load the config if it has any problems, including warnings.
+    ///
+    /// The problems keep their severity, only whether they prevent loading changes.
+    pub strict: bool,
+    /// Merge problems that differ only by an array item's index, see
+    /// [`ValidationErrors::group_by_index`].
+    pub group_by_index: bool,
+    /// The schemas to validate against instead of [`ConfigFile::schema`], the config must satisfy
+    /// every schema.
+    ///
+    /// Each schema must be able to create a validator with [`ConfigFile::validation_options`].
+    pub schemas: Vec<Value>,
+}
+
+/// A loaded config and the problems that did not prevent it from loading.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct LoadedConfig<C> {
+    /// The config.
+    pub config: C,
+    /// The warnings, and the count of suppressed problems.
+    pub problems: ValidationErrors,
+}
+
+impl LoadOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suppress problems with the code.
+    pub fn suppress<S: ToString>(mut self, code: S) -> Self {
+        self.suppress.push(code.to_string());
+        self
+    }
+
+    /// Fail to load the config if it has any problems, including warnings.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Merge problems that differ only by an array item's index, see
+    /// [`ValidationErrors::group_by_index`].
+    pub fn group_by_index(mut self) -> Self {
+        self.group_by_index = true;
+        self
+    }
+
+    /// Validate against the schema instead of [`ConfigFile::schema`], such as a schema maintained
+    /// separately from the config type.
+    ///
+    /// Calling this more than once requires the config to satisfy every schema, problems reported
+    /// by more than one schema are only reported once.
+    ///
+    /// Returns an error if the schema can't create a validator with the config's
+    /// [`ConfigFile::validation_options`], see [`json::check_schema`].
+    pub fn with_schema<C: ConfigFile>(mut self, schema: Value) -> Result<Self, SchemaError> {
+        json::check_schema(&schema, C::validation_options())?;
+        self.schemas.push(schema);
+        Ok(self)
+    }
+
+    /// Try load the config file.
+    pub fn load<C: ConfigFile>(&self) -> Result<LoadedConfig<C>, LoadConfigError> {
+        self.load_from_storage(&FileStorage)
+    }
+
+    /// Try load the config file from the storage.
+    pub fn load_from_storage<C: ConfigFile, S: ConfigStorage + ?Sized>(
+        &self,
+        storage: &S,
+    ) -> Result<LoadedConfig<C>, LoadConfigError> {
+        self.load_at(storage, C::config_file_path())
+    }
+
+    /// Try load a config file from a path other than the config file path.
+    pub fn load_from_path<C: ConfigFile>(
+        &self,
+        path: &Path,
+    ) -> Result<LoadedConfig<C>, LoadConfigError> {
+        self.load_at(&FileStorage, path.to_path_buf())
+    }
+
+    /// Try load a config from a reader, such as stdin, diagnostics reference the file as
+    /// `<stdin>`.
+    pub fn load_from_reader<C: ConfigFile, R: Read>(
+        &self,
+        reader: R,
+    ) -> Result<LoadedConfig<C>, LoadConfigError> {
+        let path = PathBuf::from(STDIN_FILE_NAME);
+        let raw_document = read_from(reader, &path)?;
+
+        self.parse(&raw_document, path)
+    }
+
+    /// Try load the config file without blocking, reading it with `tokio::fs`.
+    ///
+    /// Only the file is read asynchronously, the config is parsed and validated on the calling
+    /// task. Requires the `tokio` feature.
+    #[cfg(feature = "tokio")]
+    pub async fn load_async<C: ConfigFile>(&self) -> Result<LoadedConfig<C>, LoadConfigError> {
+        let path = C::config_file_path();
+
+        if !tokio::fs::try_exists(&path)
+            .await
+            .map_err(|source| LoadConfigError::read_error(&path, source))?
+        {
+            return Err(LoadConfigError::file_not_found(&path));
+        }
+
+        let raw_document = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|source| LoadConfigError::read_error(&path, source))?;
+
+        self.parse(&raw_document, path)
+    }
+
+    /// Validate the config file against the schema without deserializing it.
+    ///
+    /// Unlike loading, this can't fail because the schema and the config type have drifted
+    /// apart, and [`ConfigFile::validate_semantics`] is not checked as it needs the config. The
+    /// problems are returned if none of them would prevent the config from loading.
+    pub fn check<C: ConfigFile>(&self) -> Result<ValidationErrors, LoadConfigError> {
+        self.check_from_path::<C>(&C::config_file_path())
+    }
+
+    /// Validate a config file from a path other than the config file path without deserializing
+    /// it, see [`LoadOptions::check`].
+    pub fn check_from_path<C: ConfigFile>(
+        &self,
+        path: &Path,
+    ) -> Result<ValidationErrors, LoadConfigError> {
+        let raw_document = read_at(&FileStorage, path)?;
+
+        self.check_source::<C>(&raw_document, path)
+    }
+
+    /// Validate a config document that has already been read without deserializing it, such as
+    /// an edited config before it is written, see [`LoadOptions::check`].
+    ///
+    /// Diagnostics reference the document as the file at the path.
+    pub fn check_source<C: ConfigFile>(
+        &self,
+        raw_document: &str,
+        path: &Path,
+    ) -> Result<ValidationErrors, LoadConfigError> {
+        self.validate::<C>(raw_document, path.to_path_buf())
+            .map(|validated| validated.problems)
+    }
+
+    /// Validate a config from a reader without deserializing it, see [`LoadOptions::check`].
+    pub fn check_from_reader<C: ConfigFile, R: Read>(
+        &self,
+        reader: R,
+    ) -> Result<ValidationErrors, LoadConfigError> {
+        let path = PathBuf::from(STDIN_FILE_NAME);
+        let raw_document = read_from(reader, &path)?;
+
+        self.check_source::<C>(&raw_document, &path)
+    }
+
+    fn load_at<C: ConfigFile, S: ConfigStorage + ?Sized>(
+        &self,
+        storage: &S,
+        path: PathBuf,
+    ) -> Result<LoadedConfig<C>, LoadConfigError> {
+        let raw_document = read_at(storage, &path)?;
+
+        self.parse(&raw_document, path)
+    }
+
+    /// Parse, validate, and deserialize the raw config document.
+    fn parse<C: ConfigFile>(
+        &self,
+        raw_document: &str,
+        path: PathBuf,
+    ) -> Result<LoadedConfig<C>, LoadConfigError> {
+        let Validated {
+            document,
+            positioned_document,
+            mut problems,
+        } = self.validate::<C>(raw_document, path.clone())?;
+
+        // Deserialize, this can fail if problems were suppressed or the schema does not match the
+        // config.
+        let mut config = C::deserialize(&document)
+            .map_err(|source| LoadConfigError::deserialize(&path, source))?;
+
+        config.normalize(path.parent().unwrap_or(Path::new("")));
+
+        if let Err(errors) = config.validate_semantics()
+            && !errors.is_empty()
+        {
+            problems.problems.extend(errors.into_iter().map(|error| {
+                ValidationProblem::from_message_at(
+                    error.message,
+                    &error.pointer,
+                    &document,
+                    positioned_document.as_ref(),
+                    Some(path.clone()),
+                )
+            }));
+            return Err(problems.into());
+        }
+
+        Ok(LoadedConfig { config, problems })
+    }
+
+    /// Parse, migrate, and validate the raw config document, failing if any problems prevent it
+    /// from loading.
+    fn validate<C: ConfigFile>(
+        &self,
+        raw_document: &str,
+        path: PathBuf,
+    ) -> Result<Validated, LoadConfigError> {
+        // Parse the document as a node tree.
+        let mut document = serde_json::from_str::<Value>(raw_document)
+            .map_err(|source| LoadConfigError::syntax_error(&path, source, raw_document))?;
+
+        C::migrate(&mut document).map_err(|source| LoadConfigError::migrate(&path, source))?;
+
+        // Try parse the document as a node tree - recording node positions.
+        let positioned_document = PositionedJsonNode::try_parse(raw_document);
+
+        let schemas = if self.schemas.is_empty() {
+            vec![Cow::Owned(C::schema())]
+        } else {
+            self.schemas.iter().map(Cow::Borrowed).collect()
+        };
+
+        // Lint
+        let mut problems = ValidationErrors {
+            file_path: Some(path.clone()),
+            label: None,
+            problems: Vec::new(),
+            suppressed: 0,
+        };
+        for schema in &schemas {
+            problems.problems.extend(json::validate_checking_draft(
+                schema,
+                &document,
+                C::validation_options(),
+                positioned_document.as_ref(),
+                Some(&path),
+            ));
+        }
+
+        if C::warn_unknown_properties() {
+            problems.problems.extend(unknown_to_every_schema(
+                &schemas,
+                &document,
+                positioned_document.as_ref(),
+                &path,
+            ));
+        }
+
+        problems.dedupe();
+        problems.suppress(&self.suppress);
+        if self.group_by_index {
+            problems.group_by_index();
+        }
+
+        if problems
+            .problems
+            .iter()
+            .any(|problem| self.strict || problem.severity == Severity::Error)
+        {
+            return Err(problems.into());
+        }
+
+        Ok(Validated {
+            document,
+            positioned_document,
+            problems,
+        })
+    }
+}
+
+/// A parsed config document that passed validation.
+struct Validated {
+    document: Value,
+    positioned_document: Option<PositionedJsonNode>,
+    problems: ValidationErrors,
+}
+
+/// Read the raw config document from the storage.
+fn read_at<S: ConfigStorage + ?Sized>(storage: &S, path: &Path) -> Result<String, LoadConfigError> {
+    if !storage
+        .exists(path)
+        .map_err(|source| LoadConfigError::read_error(path, source))?
+    {
+        return Err(LoadConfigError::file_not_found(path));
+    }
+
+    storage
+        .read(path)
+        .map_err(|source| LoadConfigError::read_error(path, source))
+}
+
+/// Read the raw config document from the reader.
+fn read_from<R: Read>(mut reader: R, path: &Path) -> Result<String, LoadConfigError> {
+    let mut raw_document = String::new();
+    reader
+        .read_to_string(&mut raw_document)
+        .map_err(|source| LoadConfigError::read_error(path, source))?;
+
+    Ok(raw_document)
+}
+
+/// The unknown properties that are not known to any of the schemas, a property only one schema
+/// describes is still known.
+fn unknown_to_every_schema(
+    schemas: &[Cow<'_, Value>],
+    document: &Value,
+    positioned_document: Option<&PositionedJsonNode>,
+    path: &Path,
+) -> Vec<ValidationProblem> {
+    let mut schemas = schemas.iter();
+    let Some(first) = schemas.next() else {
+        return Vec::new();
+    };
+
+    let mut unknown = json::unknown_properties(
+        first,
+        document,
+        positioned_document,
+        Some(path.to_path_buf()),
+    );
+    for schema in schemas {
+        let paths: HashSet<_> = json::unknown_properties(schema, document, None, None)
+            .into_iter()
+            .map(|problem| problem.instance_path)
+            .collect();
+        unknown.retain(|problem| paths.contains(&problem.instance_path));
+    }
+
+    unknown
+}
+
+impl<C> LoadedConfig<C> {
+    /// Log any warnings and return the config.
+    pub fn log_warnings(self) -> C {
+        if !self.problems.problems.is_empty() {
+            #[cfg(feature = "log")]
+            log::warn!("{}", self.problems);
+            #[cfg(not(feature = "log"))]
+            eprintln!("{}", self.problems);
+        }
+
+        self.config
+    }
+}
@@ -0,0 +1,482 @@
+//! Helpers for application config.
+//!
+
+mod load_options;
+mod platform;
+mod schema_diff;
+mod schema_hash;
+mod storage;
+mod value_diff;
+
+use core::{error::Error, fmt};
+use std::{
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use jsonschema::ValidationOptions;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Serializer, ser::PrettyFormatter};
+
+use crate::json::{ValidationErrors, ValidationProblem};
+
+pub use load_options::{LoadOptions, LoadedConfig};
+pub use platform::platform_config_path;
+pub use schema_diff::{SchemaChange, SchemaChangeKind, schema_diff};
+#[cfg(feature = "test-util")]
+pub use storage::MemoryStorage;
+pub use storage::{ConfigStorage, FileStorage, write_with_retry};
+pub use value_diff::{PathChange, PathChangeKind, diff_values};
+
+/// The file name used in diagnostics for configs read from stdin.
+pub const STDIN_FILE_NAME: &str = "<stdin>";
+
+/// Defined behaviours for a config file.
+pub trait ConfigFile: Default + DeserializeOwned + Serialize {
+    /// The path to the config file, see [`platform_config_path`] for the conventional location.
+    fn config_file_path() -> PathBuf;
+
+    /// Return the JSON schema for the config.
+    fn schema() -> serde_json::Value;
+
+    /// An example config built from the schema, a richer starting point than the default config
+    /// as every property is populated.
+    ///
+    /// Defaults to [`json::example`](crate::json::example) of the schema, which uses the schema's
+    /// `examples` and `default`s where they are given.
+    fn example() -> serde_json::Value {
+        crate::json::example(&Self::schema())
+    }
+
+    /// A stable hash of the schema, for detecting schema changes such as to invalidate cached
+    /// artifacts.
+    ///
+    /// The schema's JSON is canonicalized with sorted keys before hashing, so the hash does not
+    /// depend on the order of the properties, and the hash is stable across builds and platforms.
+    fn schema_hash() -> u64 {
+        schema_hash::hash_schema(&Self::schema())
+    }
+
+    /// Canonicalize the config after it is loaded, such as resolving relative paths against the
+    /// directory the config was loaded from.
+    ///
+    /// This is called after the config is validated and deserialized. Configs read from a reader
+    /// have an empty config directory, so relative paths stay relative to the current directory.
+    ///
+    /// Defaults to leaving the config unchanged.
+    fn normalize(&mut self, config_dir: &Path) {
+        let _ = config_dir;
+    }
+
+    /// The options used to validate the config against the schema, such as custom keywords and
+    /// formats.
+    ///
+    /// Problems raised by custom keywords are reported like any other problem, using the message
+    /// from their [`ValidationError::custom`](jsonschema::ValidationError::custom).
+    ///
+    /// Defaults to [`ValidationOptions::default`], which only annotates formats, return
+    /// [`json::strict_options`](crate::json::strict_options) to reject values that don't match
+    /// their format.
+    fn validation_options() -> ValidationOptions {
+        ValidationOptions::default()
+    }
+
+    /// Whether loading the config should warn about properties the schema allows but does not
+    /// describe, these are often typos or deprecated settings.
+    ///
+    /// Defaults to `false`.
+    fn warn_unknown_properties() -> bool {
+        false
+    }
+
+    /// Upgrade a config written by an older version of the application before it is validated.
+    ///
+    /// This is called with the parsed config document after it is parsed and before it is
+    /// validated against the schema and deserialized, so the document only needs to match the
+    /// current schema once migrated. The migrated document is not written back to the config file.
+    ///
+    /// Problems are located using the unmigrated document, so problems in migrated values may
+    /// point to the wrong place.
+    ///
+    /// Loading does not apply any overrides from the environment. Applications that override
+    /// values from environment variables should apply them to the loaded config. Migration then
+    /// only sees the document from the file, and overrides are never migrated.
+    ///
+    /// Defaults to leaving the document unchanged.
+    fn migrate(value: &mut serde_json::Value) -> Result<(), MigrationError> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Check rules the schema can't express, such as a start being before an end.
+    ///
+    /// This is called after the config is deserialized and normalized. Each error is reported as
+    /// a problem at the error's pointer, rendered like the problems found validating against the
+    /// schema, and prevents the config from loading.
+    ///
+    /// Defaults to no errors.
+    fn validate_semantics(&self) -> Result<(), Vec<SemanticError>> {
+        Ok(())
+    }
+
+    /// The indentation used when writing the config file.
+    ///
+    /// Defaults to two spaces.
+    fn indentation() -> Indentation {
+        Indentation::Spaces(2)
+    }
+
+    /// Serialize the config as pretty printed JSON, using the config's indentation.
+    fn to_json(&self) -> serde_json::Result<Vec<u8>> {
+        let indent = Self::indentation().to_string();
+        let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+
+        let mut json = Vec::new();
+        let mut serializer = Serializer::with_formatter(&mut json, formatter);
+        self.serialize(&mut serializer)?;
+
+        Ok(json)
+    }
+
+    /// Delete the config file.
+    fn delete(&self) -> io::Result<()> {
+        self.delete_from_storage(&FileStorage)
+    }
+
+    /// Write the config file to the config file path, see [`write_to`](ConfigFile::write_to).
+    fn write(&self) -> io::Result<()> {
+        self.write_to(&Self::config_file_path())
+    }
+
+    /// Write the config file to a path, such as to export the config somewhere other than the
+    /// config file path.
+    ///
+    /// By default this writes the pretty printed config, retrying transient failures.
+    fn write_to(&self, path: &Path) -> io::Result<()> {
+        let json = self.to_json().map_err(io::Error::other)?;
+        FileStorage.write(path, &json)
+    }
+
+    /// Delete the config file from the storage.
+    fn delete_from_storage<S: ConfigStorage + ?Sized>(&self, storage: &S) -> io::Result<()> {
+        storage.delete(&Self::config_file_path())
+    }
+
+    /// Write the pretty printed config file to the storage.
+    fn write_to_storage<S: ConfigStorage + ?Sized>(&self, storage: &S) -> io::Result<()> {
+        let json = self.to_json().map_err(io::Error::other)?;
+        storage.write(&Self::config_file_path(), &json)
+    }
+}
+
+/// The indentation of a config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Indentation {
+    /// Indent with a number of spaces.
+    Spaces(usize),
+    /// Indent with a tab.
+    Tabs,
+}
+impl fmt::Display for Indentation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Spaces(count) => write!(f, "{}", " ".repeat(*count)),
+            Self::Tabs => write!(f, "\t"),
+        }
+    }
+}
+
+/// Try load a config file.
+pub fn try_load_config<C: ConfigFile>() -> Result<C, LoadConfigError> {
+    try_load_config_from_storage(&FileStorage)
+}
+
+/// Try load a config file without blocking the async runtime, see [`LoadOptions::load_async`].
+///
+/// Requires the `tokio` feature.
+#[cfg(feature = "tokio")]
+pub async fn try_load_config_async<C: ConfigFile>() -> Result<C, LoadConfigError> {
+    LoadOptions::default()
+        .load_async()
+        .await
+        .map(LoadedConfig::log_warnings)
+}
+
+/// Try load a config file from the storage.
+pub fn try_load_config_from_storage<C: ConfigFile, S: ConfigStorage + ?Sized>(
+    storage: &S,
+) -> Result<C, LoadConfigError> {
+    LoadOptions::default()
+        .load_from_storage(storage)
+        .map(LoadedConfig::log_warnings)
+}
+
+/// Try load a config file from a path other than the config file path.
+pub fn try_load_config_from_path<C: ConfigFile>(path: &Path) -> Result<C, LoadConfigError> {
+    LoadOptions::default()
+        .load_from_path(path)
+        .map(LoadedConfig::log_warnings)
+}
+
+/// Try load a config from a reader, such as stdin, diagnostics reference the file as `<stdin>`.
+pub fn try_load_config_from_reader<C: ConfigFile, R: Read>(
+    reader: R,
+) -> Result<C, LoadConfigError> {
+    LoadOptions::default()
+        .load_from_reader(reader)
+        .map(LoadedConfig::log_warnings)
+}
+
+/// Error variants from loading the config.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum LoadConfigError {
+    #[non_exhaustive]
+    FileNotFound { path: PathBuf },
+
+    #[non_exhaustive]
+    ReadError { path: PathBuf, source: io::Error },
+
+    #[non_exhaustive]
+    InvalidJson {
+        path: PathBuf,
+        source: serde_json::Error,
+        problem: Option<Box<ValidationProblem>>,
+    },
+
+    #[non_exhaustive]
+    Migrate {
+        path: PathBuf,
+        source: MigrationError,
+    },
+
+    #[non_exhaustive]
+    ValidationError { source: ValidationErrors },
+
+    #[non_exhaustive]
+    Deserialize {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+impl LoadConfigError {
+    #![allow(missing_docs)]
+    pub fn file_not_found(path: &Path) -> Self {
+        Self::FileNotFound {
+            path: path.to_owned(),
+        }
+    }
+    pub fn read_error(path: &Path, source: io::Error) -> Self {
+        Self::ReadError {
+            path: path.to_owned(),
+            source,
+        }
+    }
+    pub fn invalid_json(path: &Path, source: serde_json::Error) -> Self {
+        Self::InvalidJson {
+            path: path.to_owned(),
+            source,
+            problem: None,
+        }
+    }
+    pub fn syntax_error(path: &Path, source: serde_json::Error, raw_document: &str) -> Self {
+        let problem =
+            ValidationProblem::from_syntax_error(&source, raw_document, Some(path.to_owned()));
+
+        Self::InvalidJson {
+            path: path.to_owned(),
+            source,
+            problem: Some(Box::new(problem)),
+        }
+    }
+    pub fn migrate(path: &Path, source: MigrationError) -> Self {
+        Self::Migrate {
+            path: path.to_owned(),
+            source,
+        }
+    }
+    pub fn validation_error(source: ValidationErrors) -> Self {
+        Self::ValidationError { source }
+    }
+    pub fn deserialize(path: &Path, source: serde_json::Error) -> Self {
+        Self::Deserialize {
+            path: path.to_owned(),
+            source,
+        }
+    }
+}
+impl LoadConfigError {
+    /// The validation problems, if the config failed validation.
+    pub fn validation_errors(&self) -> Option<&ValidationErrors> {
+        match &self {
+            Self::ValidationError { source } => Some(source),
+            _ => None,
+        }
+    }
+}
+impl LoadConfigError {
+    /// Convert the error into validation problems, so configs that could not be loaded can be
+    /// reported alongside configs with validation problems.
+    ///
+    /// Errors without validation problems become a single problem at the root of the config with
+    /// the error and its sources as the message.
+    pub fn into_validation_errors(self) -> ValidationErrors {
+        let (path, problem) = match self {
+            Self::ValidationError { source } => return source,
+            Self::InvalidJson {
+                path,
+                problem: Some(problem),
+                ..
+            } => (path, *problem),
+            error => {
+                let path = match &error {
+                    Self::FileNotFound { path }
+                    | Self::ReadError { path, .. }
+                    | Self::InvalidJson { path, .. }
+                    | Self::Migrate { path, .. }
+                    | Self::Deserialize { path, .. } => path.clone(),
+                    Self::ValidationError { .. } => PathBuf::new(),
+                };
+
+                let mut messages = vec![error.to_string()];
+                let mut source = error.source();
+                while let Some(error) = source {
+                    messages.push(error.to_string());
+                    source = error.source();
+                }
+
+                let problem =
+                    ValidationProblem::from_message(messages.join(": "), Some(path.clone()));
+                (path, problem)
+            }
+        };
+
+        ValidationErrors {
+            file_path: Some(path),
+            label: None,
+            problems: vec![problem],
+            suppressed: 0,
+        }
+    }
+}
+impl From<ValidationErrors> for LoadConfigError {
+    fn from(value: ValidationErrors) -> Self {
+        Self::validation_error(value)
+    }
+}
+impl fmt::Display for LoadConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::FileNotFound { path, .. } => {
+                write!(f, "config file `{}` does not exist", path.to_string_lossy())
+            }
+            Self::ReadError { path, .. } => {
+                write!(f, "could not read config file `{}`", path.to_string_lossy())
+            }
+            Self::InvalidJson { path, problem, .. } => {
+                write!(
+                    f,
+                    "config file `{}` is not valid JSON",
+                    path.to_string_lossy()
+                )?;
+                if let Some(problem) = problem {
+                    write!(f, ":\n{problem}")?;
+                }
+                Ok(())
+            }
+            Self::Migrate { path, .. } => write!(
+                f,
+                "config file `{}` could not be migrated",
+                path.to_string_lossy()
+            ),
+            Self::ValidationError { source, .. } => write!(f, "{source}"),
+            Self::Deserialize { path, .. } => write!(
+                f,
+                "config file `{}` could not be deserialized",
+                path.to_string_lossy()
+            ),
+        }
+    }
+}
+impl Error for LoadConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            Self::ReadError { source, .. } => Some(source),
+            // The rendered problem already describes the source.
+            Self::InvalidJson {
+                problem: Some(_), ..
+            } => None,
+            Self::InvalidJson { source, .. } => Some(source),
+            Self::Migrate { source, .. } => Some(source),
+            Self::Deserialize { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A config breaks a rule the schema can't express, see [`ConfigFile::validate_semantics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SemanticError {
+    /// The JSON pointer to the value that breaks the rule, such as `/schedule/end`.
+    pub pointer: String,
+    /// What is wrong with the value, such as `this should be after the start`.
+    pub message: String,
+}
+impl SemanticError {
+    #![allow(missing_docs)]
+    pub fn new(pointer: impl ToString, message: impl ToString) -> Self {
+        Self {
+            pointer: pointer.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+impl fmt::Display for SemanticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` {}", self.pointer, self.message)
+    }
+}
+impl Error for SemanticError {}
+
+/// A config document could not be migrated to the current version.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub struct MigrationError {
+    pub message: String,
+    pub source: Option<Box<dyn Error + Send + Sync + 'static>>,
+}
+impl MigrationError {
+    #![allow(missing_docs)]
+    pub fn new<S: ToString>(message: S) -> Self {
+        Self {
+            message: message.to_string(),
+            source: None,
+        }
+    }
+    pub fn with_source<E: Error + Send + Sync + 'static>(
+        message: impl ToString,
+        source: E,
+    ) -> Self {
+        Self {
+            message: message.to_string(),
+            source: Some(Box::new(source)),
+        }
+    }
+}
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+impl Error for MigrationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_ref()),
+            None => None,
+        }
+    }
+}
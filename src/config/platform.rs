@@ -0,0 +1,33 @@
+use std::{env, path::PathBuf};
+
+/// The conventional path for an application's config file on the current platform.
+///
+/// * Linux and other Unix: `$XDG_CONFIG_HOME/<app_name>/<file_name>`, falling back to
+///   `~/.config/<app_name>/<file_name>`.
+/// * macOS: `~/Library/Application Support/<app_name>/<file_name>`.
+/// * Windows: `%APPDATA%\<app_name>\<file_name>`.
+///
+/// If the platform's directory can't be found from the environment, the path is relative to the
+/// current directory, `<app_name>/<file_name>`.
+pub fn platform_config_path(app_name: &str, file_name: &str) -> PathBuf {
+    config_directory()
+        .unwrap_or_default()
+        .join(app_name)
+        .join(file_name)
+}
+
+/// The platform's config directory from the environment.
+fn config_directory() -> Option<PathBuf> {
+    let non_empty = |key| env::var_os(key).filter(|value| !value.is_empty());
+
+    if cfg!(windows) {
+        non_empty("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        non_empty("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        non_empty("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }
+}
@@ -0,0 +1,398 @@
+use core::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::json::escape_segment;
+
+/// The keywords that set a lower bound, raising them narrows the accepted values.
+const LOWER_BOUNDS: [&str; 5] = [
+    "minimum",
+    "exclusiveMinimum",
+    "minLength",
+    "minItems",
+    "minProperties",
+];
+/// The keywords that set an upper bound, lowering them narrows the accepted values.
+const UPPER_BOUNDS: [&str; 5] = [
+    "maximum",
+    "exclusiveMaximum",
+    "maxLength",
+    "maxItems",
+    "maxProperties",
+];
+/// The keywords that constrain the accepted values in a way that can't be compared, so any change
+/// to them is treated as narrowing.
+const OPAQUE_CONSTRAINTS: [&str; 5] = ["const", "pattern", "format", "multipleOf", "$ref"];
+
+/// Find the changes between two versions of a config schema.
+///
+/// A change is breaking if a config that was valid against the old schema may be invalid against
+/// the new schema. The schemas are compared structurally, following `properties`, `items`,
+/// `prefixItems`, `additionalProperties`, `$defs`, and `definitions` into the subschemas present
+/// in both versions, `$ref`s are compared but not resolved.
+///
+/// The detected changes are:
+/// * A property being added or removed, removing a property is breaking if additional properties
+///   are not allowed.
+/// * A property becoming required (breaking) or optional.
+/// * Types being removed (breaking) or added to `type`.
+/// * Values being removed (breaking) or added to `enum`.
+/// * Additional properties becoming disallowed (breaking) or allowed.
+/// * A bound such as `minimum` or `maxLength` being tightened (breaking) or loosened.
+/// * `const`, `pattern`, `format`, `multipleOf`, or `$ref` being added or changed (breaking) or
+///   removed.
+pub fn schema_diff(old: &Value, new: &Value) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    diff(old, new, String::new(), &mut changes);
+    changes
+}
+
+/// A change between two versions of a schema.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SchemaChange {
+    /// The JSON pointer to the changed subschema.
+    pub pointer: String,
+    /// The kind of change.
+    pub kind: SchemaChangeKind,
+    /// If the change may make a valid config invalid.
+    pub breaking: bool,
+}
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "[root]"
+        } else {
+            &self.pointer
+        };
+        let compatibility = if self.breaking {
+            "breaking"
+        } else {
+            "compatible"
+        };
+
+        write!(f, "{compatibility}: `{pointer}` {}", self.kind)
+    }
+}
+
+/// The kinds of change between two versions of a schema.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum SchemaChangeKind {
+    #[non_exhaustive]
+    PropertyAdded { property: String },
+
+    #[non_exhaustive]
+    PropertyRemoved { property: String },
+
+    #[non_exhaustive]
+    PropertyRequired { property: String },
+
+    #[non_exhaustive]
+    PropertyOptional { property: String },
+
+    #[non_exhaustive]
+    TypeRemoved { json_type: String },
+
+    #[non_exhaustive]
+    TypeAdded { json_type: String },
+
+    #[non_exhaustive]
+    EnumValueRemoved { value: Value },
+
+    #[non_exhaustive]
+    EnumValueAdded { value: Value },
+
+    #[non_exhaustive]
+    AdditionalPropertiesDisallowed,
+
+    #[non_exhaustive]
+    AdditionalPropertiesAllowed,
+
+    #[non_exhaustive]
+    ConstraintChanged {
+        keyword: String,
+        old: Option<Value>,
+        new: Option<Value>,
+    },
+}
+impl fmt::Display for SchemaChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::PropertyAdded { property } => write!(f, "added the property `{property}`"),
+            Self::PropertyRemoved { property } => write!(f, "removed the property `{property}`"),
+            Self::PropertyRequired { property } => {
+                write!(f, "made the property `{property}` required")
+            }
+            Self::PropertyOptional { property } => {
+                write!(f, "made the property `{property}` optional")
+            }
+            Self::TypeRemoved { json_type } => write!(f, "no longer accepts {json_type}"),
+            Self::TypeAdded { json_type } => write!(f, "now accepts {json_type}"),
+            Self::EnumValueRemoved { value } => write!(f, "no longer accepts {value}"),
+            Self::EnumValueAdded { value } => write!(f, "now accepts {value}"),
+            Self::AdditionalPropertiesDisallowed => {
+                write!(f, "no longer allows additional properties")
+            }
+            Self::AdditionalPropertiesAllowed => write!(f, "now allows additional properties"),
+            Self::ConstraintChanged { keyword, old, new } => match (old, new) {
+                (Some(old), Some(new)) => write!(f, "changed `{keyword}` from {old} to {new}"),
+                (None, Some(new)) => write!(f, "added `{keyword}` of {new}"),
+                (Some(old), None) => write!(f, "removed `{keyword}` of {old}"),
+                (None, None) => write!(f, "changed `{keyword}`"),
+            },
+        }
+    }
+}
+
+fn diff(old: &Value, new: &Value, pointer: String, changes: &mut Vec<SchemaChange>) {
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return;
+    };
+
+    let mut push = |kind, breaking| {
+        changes.push(SchemaChange {
+            pointer: pointer.clone(),
+            kind,
+            breaking,
+        });
+    };
+
+    // Properties
+    let old_properties = keys(old.get("properties"));
+    let new_properties = keys(new.get("properties"));
+    let disallows_additional = new.get("additionalProperties") == Some(&Value::Bool(false));
+    for property in new_properties
+        .iter()
+        .filter(|p| !old_properties.contains(p))
+    {
+        push(
+            SchemaChangeKind::PropertyAdded {
+                property: property.clone(),
+            },
+            false,
+        );
+    }
+    for property in old_properties
+        .iter()
+        .filter(|p| !new_properties.contains(p))
+    {
+        push(
+            SchemaChangeKind::PropertyRemoved {
+                property: property.clone(),
+            },
+            disallows_additional,
+        );
+    }
+
+    // Required
+    let old_required = strings(old.get("required"));
+    let new_required = strings(new.get("required"));
+    for property in new_required.iter().filter(|p| !old_required.contains(p)) {
+        push(
+            SchemaChangeKind::PropertyRequired {
+                property: property.clone(),
+            },
+            true,
+        );
+    }
+    for property in old_required.iter().filter(|p| !new_required.contains(p)) {
+        push(
+            SchemaChangeKind::PropertyOptional {
+                property: property.clone(),
+            },
+            false,
+        );
+    }
+
+    // Types, an absent `type` accepts every type.
+    if let Some(new_types) = new.get("type") {
+        let new_types = strings(Some(new_types));
+        match old.get("type") {
+            Some(old_types) => {
+                let old_types = strings(Some(old_types));
+                for json_type in old_types.iter().filter(|t| !accepts_type(&new_types, t)) {
+                    push(
+                        SchemaChangeKind::TypeRemoved {
+                            json_type: json_type.clone(),
+                        },
+                        true,
+                    );
+                }
+                for json_type in new_types.iter().filter(|t| !accepts_type(&old_types, t)) {
+                    push(
+                        SchemaChangeKind::TypeAdded {
+                            json_type: json_type.clone(),
+                        },
+                        false,
+                    );
+                }
+            }
+            None => push(
+                SchemaChangeKind::ConstraintChanged {
+                    keyword: "type".to_string(),
+                    old: None,
+                    new: new.get("type").cloned(),
+                },
+                true,
+            ),
+        }
+    }
+
+    // Enum, an absent `enum` accepts every value.
+    if let Some(new_values) = new.get("enum").and_then(Value::as_array) {
+        match old.get("enum").and_then(Value::as_array) {
+            Some(old_values) => {
+                for value in old_values.iter().filter(|v| !new_values.contains(v)) {
+                    push(
+                        SchemaChangeKind::EnumValueRemoved {
+                            value: value.clone(),
+                        },
+                        true,
+                    );
+                }
+                for value in new_values.iter().filter(|v| !old_values.contains(v)) {
+                    push(
+                        SchemaChangeKind::EnumValueAdded {
+                            value: value.clone(),
+                        },
+                        false,
+                    );
+                }
+            }
+            None => push(
+                SchemaChangeKind::ConstraintChanged {
+                    keyword: "enum".to_string(),
+                    old: None,
+                    new: new.get("enum").cloned(),
+                },
+                true,
+            ),
+        }
+    }
+
+    // Additional properties
+    let old_disallows_additional = old.get("additionalProperties") == Some(&Value::Bool(false));
+    if disallows_additional && !old_disallows_additional {
+        push(SchemaChangeKind::AdditionalPropertiesDisallowed, true);
+    } else if !disallows_additional && old_disallows_additional {
+        push(SchemaChangeKind::AdditionalPropertiesAllowed, false);
+    }
+
+    // Bounds
+    let raised: fn(f64, f64) -> bool = |old, new| new > old;
+    let lowered: fn(f64, f64) -> bool = |old, new| new < old;
+    for (keywords, tightened) in [(LOWER_BOUNDS, raised), (UPPER_BOUNDS, lowered)] {
+        for keyword in keywords {
+            let old_bound = old.get(keyword);
+            let new_bound = new.get(keyword);
+            let breaking = match (
+                old_bound.and_then(Value::as_f64),
+                new_bound.and_then(Value::as_f64),
+            ) {
+                (Some(old), Some(new)) if old == new => continue,
+                (Some(old), Some(new)) => tightened(old, new),
+                (None, None) => continue,
+                (None, Some(_)) => true,
+                (Some(_), None) => false,
+            };
+
+            push(
+                SchemaChangeKind::ConstraintChanged {
+                    keyword: keyword.to_string(),
+                    old: old_bound.cloned(),
+                    new: new_bound.cloned(),
+                },
+                breaking,
+            );
+        }
+    }
+
+    // Opaque constraints
+    for keyword in OPAQUE_CONSTRAINTS {
+        let old_constraint = old.get(keyword);
+        let new_constraint = new.get(keyword);
+        if old_constraint == new_constraint {
+            continue;
+        }
+
+        push(
+            SchemaChangeKind::ConstraintChanged {
+                keyword: keyword.to_string(),
+                old: old_constraint.cloned(),
+                new: new_constraint.cloned(),
+            },
+            new_constraint.is_some(),
+        );
+    }
+
+    // Subschemas
+    for keyword in ["properties", "$defs", "definitions"] {
+        let (Some(old_children), Some(new_children)) = (
+            old.get(keyword).and_then(Value::as_object),
+            new.get(keyword).and_then(Value::as_object),
+        ) else {
+            continue;
+        };
+
+        for (key, old_child) in old_children {
+            if let Some(new_child) = new_children.get(key) {
+                let child_pointer = format!("{pointer}/{keyword}/{}", escape_segment(key));
+                diff(old_child, new_child, child_pointer, changes);
+            }
+        }
+    }
+
+    for keyword in ["items", "additionalProperties"] {
+        if let (Some(old_child), Some(new_child)) = (old.get(keyword), new.get(keyword)) {
+            diff(
+                old_child,
+                new_child,
+                format!("{pointer}/{keyword}"),
+                changes,
+            );
+        }
+    }
+
+    if let (Some(old_items), Some(new_items)) = (
+        old.get("prefixItems").and_then(Value::as_array),
+        new.get("prefixItems").and_then(Value::as_array),
+    ) {
+        for (index, (old_item, new_item)) in old_items.iter().zip(new_items).enumerate() {
+            diff(
+                old_item,
+                new_item,
+                format!("{pointer}/prefixItems/{index}"),
+                changes,
+            );
+        }
+    }
+}
+
+/// The keys of an object.
+fn keys(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_object)
+        .map(Map::keys)
+        .map_or_else(Vec::new, |keys| keys.cloned().collect())
+}
+
+/// The strings in a string or an array of strings.
+fn strings(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(value)) => vec![value.clone()],
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Check if the types accept the type, `number` accepts `integer`.
+fn accepts_type(types: &[String], json_type: &str) -> bool {
+    types.iter().any(|t| t == json_type)
+        || (json_type == "integer" && types.iter().any(|t| t == "number"))
+}
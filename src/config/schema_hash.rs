@@ -0,0 +1,59 @@
+use serde_json::Value;
+
+/// The FNV-1a 64 bit offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The FNV-1a 64 bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hash the canonical JSON of a schema, with object keys sorted and no whitespace.
+///
+/// FNV-1a is used as it is fully specified, so the hash is stable across Rust versions and
+/// platforms.
+pub(crate) fn hash_schema(schema: &Value) -> u64 {
+    let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+    write_canonical(schema, &mut hasher);
+    hasher.0
+}
+
+struct Fnv1a(u64);
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+fn write_canonical(value: &Value, hasher: &mut Fnv1a) {
+    match value {
+        Value::Array(items) => {
+            hasher.write(b"[");
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    hasher.write(b",");
+                }
+                write_canonical(item, hasher);
+            }
+            hasher.write(b"]");
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+
+            hasher.write(b"{");
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    hasher.write(b",");
+                }
+                hasher.write(Value::String(key.clone()).to_string().as_bytes());
+                hasher.write(b":");
+                write_canonical(value, hasher);
+            }
+            hasher.write(b"}");
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {
+            hasher.write(value.to_string().as_bytes());
+        }
+    }
+}
@@ -0,0 +1,164 @@
+#[cfg(feature = "test-util")]
+use std::{collections::HashMap, sync::Mutex};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+/// The number of attempts the [`FileStorage`] makes to write a file.
+const WRITE_ATTEMPTS: usize = 5;
+/// The delay between attempts for the [`FileStorage`] to write a file.
+const WRITE_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// The number of temporary files created by this process, so each write uses a unique file.
+static TEMP_FILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Storage that backs config files.
+pub trait ConfigStorage {
+    /// Returns if a file exists at the path.
+    fn exists(&self, path: &Path) -> io::Result<bool>;
+
+    /// Read the file at the path.
+    fn read(&self, path: &Path) -> io::Result<String>;
+
+    /// Write the contents to the file at the path.
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /// Delete the file at the path.
+    fn delete(&self, path: &Path) -> io::Result<()>;
+}
+
+/// Config storage backed by the filesystem.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FileStorage;
+impl ConfigStorage for FileStorage {
+    fn exists(&self, path: &Path) -> io::Result<bool> {
+        fs::exists(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        write_with_retry(path, contents, WRITE_ATTEMPTS, WRITE_RETRY_DELAY)
+    }
+
+    fn delete(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+}
+
+/// Config storage backed by memory, for testing config logic without touching the filesystem.
+#[cfg(feature = "test-util")]
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    files: Mutex<HashMap<PathBuf, Vec<u8>>>,
+}
+#[cfg(feature = "test-util")]
+impl MemoryStorage {
+    /// Create new empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file to the storage.
+    pub fn with_file<P: Into<PathBuf>, B: Into<Vec<u8>>>(self, path: P, contents: B) -> Self {
+        self.files().insert(path.into(), contents.into());
+        self
+    }
+
+    /// Get the contents of a file in the storage.
+    pub fn get(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files().get(path).cloned()
+    }
+
+    fn files(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Vec<u8>>> {
+        self.files
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+#[cfg(feature = "test-util")]
+impl ConfigStorage for MemoryStorage {
+    fn exists(&self, path: &Path) -> io::Result<bool> {
+        Ok(self.files().contains_key(path))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<String> {
+        let contents = self
+            .get(path)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        String::from_utf8(contents)
+            .map_err(|source| io::Error::new(io::ErrorKind::InvalidData, source))
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.files().insert(path.to_owned(), contents.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, path: &Path) -> io::Result<()> {
+        self.files()
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+}
+
+/// Atomically write the contents to a path, retrying transient failures.
+///
+/// Writes on Windows can fail with `PermissionDenied` while an editor or antivirus has the file
+/// open, these and `WouldBlock` errors are retried up to `attempts` times with `delay` between each
+/// attempt. The last error is returned once the attempts are exhausted.
+pub fn write_with_retry(
+    path: &Path,
+    contents: &[u8],
+    attempts: usize,
+    delay: Duration,
+) -> io::Result<()> {
+    let mut attempt = 1;
+    loop {
+        match write_atomic(path, contents) {
+            Ok(()) => return Ok(()),
+            Err(error)
+                if attempt < attempts
+                    && matches!(
+                        error.kind(),
+                        io::ErrorKind::PermissionDenied | io::ErrorKind::WouldBlock
+                    ) =>
+            {
+                attempt += 1;
+                thread::sleep(delay);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Write the contents to a temporary file next to the path, then move it into place.
+///
+/// The temporary file is unique to the write, so concurrent writers don't overwrite each other's
+/// temporary file, and it is removed if the write fails.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        TEMP_FILE_COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp_path = PathBuf::from(temp_path);
+
+    fs::write(&temp_path, contents)
+        .and_then(|()| fs::rename(&temp_path, path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
+}
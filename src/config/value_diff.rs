@@ -0,0 +1,147 @@
+use core::fmt;
+
+use serde_json::Value;
+
+use crate::json::escape_segment;
+
+/// Find the changes between two versions of a config's JSON, such as before and after reloading
+/// the config.
+///
+/// Objects are compared by key and arrays by index, a change is reported at the deepest pointer
+/// that differs, so changing one property of an object reports that property rather than the
+/// object. Values that change type are reported as modified.
+///
+/// For a live reload, keep the [`serde_json::to_value`] of the current config, load the new config
+/// with [`try_load_config`](crate::config::try_load_config), then re-initialize only the
+/// subsystems whose settings are under the changed pointers.
+pub fn diff_values(old: &Value, new: &Value) -> Vec<PathChange> {
+    let mut changes = Vec::new();
+    diff(old, new, String::new(), &mut changes);
+    changes
+}
+
+/// A change to a value between two versions of a config.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PathChange {
+    /// The JSON pointer to the changed value.
+    pub pointer: String,
+    /// The kind of change.
+    pub kind: PathChangeKind,
+}
+impl PathChange {
+    /// The value before the change, if it existed.
+    pub fn old_value(&self) -> Option<&Value> {
+        match &self.kind {
+            PathChangeKind::Added { .. } => None,
+            PathChangeKind::Removed { value } => Some(value),
+            PathChangeKind::Modified { old, .. } => Some(old),
+        }
+    }
+
+    /// The value after the change, if it exists.
+    pub fn new_value(&self) -> Option<&Value> {
+        match &self.kind {
+            PathChangeKind::Added { value } => Some(value),
+            PathChangeKind::Removed { .. } => None,
+            PathChangeKind::Modified { new, .. } => Some(new),
+        }
+    }
+}
+impl fmt::Display for PathChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "[root]"
+        } else {
+            &self.pointer
+        };
+
+        write!(f, "`{pointer}` {}", self.kind)
+    }
+}
+
+/// The kinds of change to a value.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum PathChangeKind {
+    #[non_exhaustive]
+    Added { value: Value },
+
+    #[non_exhaustive]
+    Removed { value: Value },
+
+    #[non_exhaustive]
+    Modified { old: Value, new: Value },
+}
+impl fmt::Display for PathChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Added { value } => write!(f, "was added as {value}"),
+            Self::Removed { value } => write!(f, "was removed, it was {value}"),
+            Self::Modified { old, new } => write!(f, "changed from {old} to {new}"),
+        }
+    }
+}
+
+fn diff(old: &Value, new: &Value, pointer: String, changes: &mut Vec<PathChange>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let child_pointer = format!("{pointer}/{}", escape_segment(key));
+                match new.get(key) {
+                    Some(new_value) => diff(old_value, new_value, child_pointer, changes),
+                    None => changes.push(PathChange {
+                        pointer: child_pointer,
+                        kind: PathChangeKind::Removed {
+                            value: old_value.clone(),
+                        },
+                    }),
+                }
+            }
+
+            for (key, new_value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+                changes.push(PathChange {
+                    pointer: format!("{pointer}/{}", escape_segment(key)),
+                    kind: PathChangeKind::Added {
+                        value: new_value.clone(),
+                    },
+                });
+            }
+        }
+
+        (Value::Array(old), Value::Array(new)) => {
+            for (index, (old_item, new_item)) in old.iter().zip(new).enumerate() {
+                diff(old_item, new_item, format!("{pointer}/{index}"), changes);
+            }
+
+            for (index, old_item) in old.iter().enumerate().skip(new.len()) {
+                changes.push(PathChange {
+                    pointer: format!("{pointer}/{index}"),
+                    kind: PathChangeKind::Removed {
+                        value: old_item.clone(),
+                    },
+                });
+            }
+
+            for (index, new_item) in new.iter().enumerate().skip(old.len()) {
+                changes.push(PathChange {
+                    pointer: format!("{pointer}/{index}"),
+                    kind: PathChangeKind::Added {
+                        value: new_item.clone(),
+                    },
+                });
+            }
+        }
+
+        (old, new) if old != new => changes.push(PathChange {
+            pointer,
+            kind: PathChangeKind::Modified {
+                old: old.clone(),
+                new: new.clone(),
+            },
+        }),
+
+        _ => {}
+    }
+}
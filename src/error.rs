@@ -3,16 +3,47 @@
 use core::{
     error::Error,
     fmt::{self, Write},
+    iter,
+    ops::Range,
+};
+use std::{
+    env,
+    ffi::OsStr,
+    io::{self, IsTerminal},
+    panic,
+    path::PathBuf,
+    sync::{OnceLock, mpsc},
+    time::{Duration, Instant},
 };
-use std::{env::current_exe, ffi::OsStr, path::PathBuf};
 
-use crate::style::{BOLD, RED, RESET};
+use crate::style::{BOLD, CYAN, RESET, colours, strip_ansi};
 
 /// Trait to log a result.
 pub trait ErrorLogger {
     /// Log the result
     #[track_caller]
     fn log_error(self) -> Self;
+
+    /// The formatted error, if the result is an error.
+    ///
+    /// Defaults to `None`, so [`ErrorLogger::log_error_to`] only logs the result.
+    fn error_message(&self) -> Option<String> {
+        None
+    }
+
+    /// Log the result and send the formatted error down the channel.
+    ///
+    /// A disconnected channel is ignored.
+    #[track_caller]
+    fn log_error_to(self, tx: &mpsc::Sender<String>) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(message) = self.error_message() {
+            let _ = tx.send(message);
+        }
+        self.log_error()
+    }
 }
 
 impl<T, E: fmt::Display> ErrorLogger for Result<T, E> {
@@ -26,6 +57,10 @@ impl<T, E: fmt::Display> ErrorLogger for Result<T, E> {
         }
         self
     }
+
+    fn error_message(&self) -> Option<String> {
+        self.as_ref().err().map(ToString::to_string)
+    }
 }
 impl<T> ErrorLogger for Option<T> {
     #[track_caller]
@@ -38,16 +73,82 @@ impl<T> ErrorLogger for Option<T> {
         }
         self
     }
+
+    fn error_message(&self) -> Option<String> {
+        self.is_none().then(|| "value was None".to_string())
+    }
+}
+
+/// Trait to log a result unless its error is expected.
+pub trait ErrorLoggerExt: ErrorLogger + Sized {
+    /// The error that is checked, `()` for an `Option`.
+    type Error;
+
+    /// Log the result unless the error is expected, such as a missing config file when the
+    /// default config is used instead.
+    #[track_caller]
+    fn log_error_unless<F: Fn(&Self::Error) -> bool>(self, skip: F) -> Self;
+}
+
+impl<T, E: fmt::Display> ErrorLoggerExt for Result<T, E> {
+    type Error = E;
+
+    #[track_caller]
+    fn log_error_unless<F: Fn(&Self::Error) -> bool>(self, skip: F) -> Self {
+        match self.as_ref() {
+            Err(error) if skip(error) => self,
+            _ => self.log_error(),
+        }
+    }
+}
+impl<T> ErrorLoggerExt for Option<T> {
+    type Error = ();
+
+    #[track_caller]
+    fn log_error_unless<F: Fn(&Self::Error) -> bool>(self, skip: F) -> Self {
+        if self.is_none() && skip(&()) {
+            return self;
+        }
+        self.log_error()
+    }
 }
 
 /// Type alias for a program that reports it's exit.
 pub type ReportProgramExit = Result<(), ProgramReport>;
 
 /// A report for a program exit.
-pub struct ProgramReport(Box<dyn Error + 'static>);
+pub struct ProgramReport {
+    source: ProgramReportSource,
+    elapsed: Option<Duration>,
+}
+enum ProgramReportSource {
+    Error(Box<dyn Error + 'static>),
+    Rendered(String),
+}
+impl ProgramReport {
+    /// Create a program report from a report that may borrow it's source.
+    ///
+    /// The report is rendered when the program report is created, so the program report does not
+    /// borrow from the source. The source error can no longer be downcast or inspected.
+    pub fn from_report(report: Report<'_>) -> Self {
+        Self {
+            source: ProgramReportSource::Rendered(report.to_string()),
+            elapsed: None,
+        }
+    }
+
+    /// Report how long the program ran since the start before failing.
+    pub fn with_elapsed(mut self, start: Instant) -> Self {
+        self.elapsed = Some(start.elapsed());
+        self
+    }
+}
 impl<E: Error + 'static> From<E> for ProgramReport {
     fn from(value: E) -> Self {
-        Self(Box::new(value))
+        Self {
+            source: ProgramReportSource::Error(Box::new(value)),
+            elapsed: None,
+        }
     }
 }
 impl fmt::Debug for ProgramReport {
@@ -57,17 +158,87 @@ impl fmt::Debug for ProgramReport {
 }
 impl fmt::Display for ProgramReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let exe_path = current_exe().unwrap_or_else(|_| PathBuf::from("program"));
-        let exe = exe_path
-            .file_name()
-            .unwrap_or_else(|| OsStr::new("program"))
-            .to_string_lossy();
+        match &self.source {
+            ProgramReportSource::Error(source) => {
+                let report = Report::new(
+                    program_name(),
+                    source.as_ref(),
+                    ErrorStackStyle::Stacked { indent: 2 },
+                );
+                write!(f, "{report}")?;
+            }
+            ProgramReportSource::Rendered(report) => write!(f, "{report}")?,
+        }
 
-        let report = Report::new(exe, self.0.as_ref(), ErrorStackStyle::Stacked { indent: 2 });
-        write!(f, "{report}")
+        if let Some(elapsed) = self.elapsed {
+            writeln!(f, "failed after {:.1}s", elapsed.as_secs_f64())?;
+        }
+
+        Ok(())
     }
 }
 
+/// Run the program, reporting how long it ran if it fails.
+///
+/// Returning a `ReportProgramExit` directly from `main` does not track the elapsed time.
+pub fn run_timed<F: FnOnce() -> ReportProgramExit>(program: F) -> ReportProgramExit {
+    let start = Instant::now();
+    program().map_err(|report| report.with_elapsed(start))
+}
+
+/// Replace the default panic hook with one that prints panics as a report, styled like a
+/// [`ProgramReport`].
+///
+/// The report names the program, the panic message, and where the panic occurred. Styling is
+/// removed when stderr is not a terminal or `NO_COLOR` is set. The previous hook, including the
+/// default hook and its backtrace printing, is no longer called.
+pub fn install_panic_report_hook() {
+    panic::set_hook(Box::new(|info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".to_string());
+
+        let panicked = match info.location() {
+            Some(location) => format!("the program panicked at `{location}`"),
+            None => "the program panicked".to_string(),
+        };
+        let source = StringError {
+            message: panicked,
+            source: Some(Box::new(StringError {
+                message,
+                source: None,
+            })),
+        };
+
+        let report = Report::new(program_name(), source, ErrorStackStyle::default()).to_string();
+        let use_colour = io::stderr().is_terminal()
+            && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty());
+
+        if use_colour {
+            eprint!("{report}");
+        } else {
+            eprint!("{}", strip_ansi(&report));
+        }
+    }));
+}
+
+/// The file name of the running program, or `program` if it is unknown.
+pub(crate) fn program_name() -> &'static str {
+    static PROGRAM_NAME: OnceLock<String> = OnceLock::new();
+
+    PROGRAM_NAME.get_or_init(|| {
+        let exe_path = env::current_exe().unwrap_or_else(|_| PathBuf::from("program"));
+        exe_path
+            .file_name()
+            .unwrap_or_else(|| OsStr::new("program"))
+            .to_string_lossy()
+            .into_owned()
+    })
+}
+
 /// Extension trait for reporting a result
 pub trait IntoErrorReport<'a, T>: Sized {
     /// Convert the result into a report.
@@ -96,6 +267,7 @@ impl<'a, T> IntoErrorReport<'a, T> for Option<T> {
 }
 
 /// A report of an error.
+#[non_exhaustive]
 pub struct Report<'a> {
     /// The source of the error.
     pub source: Box<dyn Error + 'a>,
@@ -103,6 +275,8 @@ pub struct Report<'a> {
     pub style: ErrorStackStyle<'a>,
     /// The operation this report is for.
     pub operation: String,
+    /// The source code the error is about.
+    pub snippet: Option<Box<Snippet>>,
 }
 impl<'a> Report<'a> {
     /// Create a new report.
@@ -115,8 +289,79 @@ impl<'a> Report<'a> {
             source: Box::new(source),
             style,
             operation: operation.to_string(),
+            snippet: None,
+        }
+    }
+
+    /// Attach the source code the error is about, rendered below the errors like a compiler
+    /// diagnostic.
+    pub fn with_snippet(mut self, snippet: Snippet) -> Self {
+        self.snippet = Some(Box::new(snippet));
+        self
+    }
+
+    /// Iterate over the chain of errors, starting with the report's source.
+    ///
+    /// The errors of a `Report<'static>` can be downcast, such as with
+    /// `report.chain().any(|error| error.is::<io::Error>())`.
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn Error + 'a)> {
+        chain(self.source.as_ref())
+    }
+
+    /// Create an owned copy of the report, rendering each error in the chain to its message.
+    ///
+    /// The snapshot preserves the messages but the errors can no longer be downcast. A custom
+    /// style can't be copied so is replaced with the default style.
+    pub fn snapshot(&self) -> Report<'static> {
+        let messages: Vec<String> = self.chain().map(|error| error.to_string()).collect();
+        let source = messages
+            .into_iter()
+            .rev()
+            .fold(None, |source, message| {
+                Some(StringError {
+                    message,
+                    source: source.map(Box::new),
+                })
+            })
+            .unwrap_or_default();
+
+        let style = match self.style {
+            ErrorStackStyle::Inline => ErrorStackStyle::Inline,
+            ErrorStackStyle::Stacked { indent } => ErrorStackStyle::Stacked { indent },
+            ErrorStackStyle::Custom(_) => ErrorStackStyle::default(),
+        };
+
+        Report {
+            source: Box::new(source),
+            style,
+            operation: self.operation.clone(),
+            snippet: self.snippet.clone(),
         }
     }
+
+    /// Render the report into a string report, which can be cloned and sent between threads.
+    pub fn to_owned_string_report(&self) -> StringReport {
+        StringReport(self.to_string())
+    }
+}
+/// Cloning a report clones its [`snapshot`](Report::snapshot), so the clone's errors can't be
+/// downcast.
+impl Clone for Report<'_> {
+    fn clone(&self) -> Self {
+        self.snapshot()
+    }
+}
+impl Report<'static> {
+    /// Downcast the report's source, the immediate error, to a concrete error type.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.source.downcast_ref()
+    }
+
+    /// Find the first error in the chain of the type, such as an `io::Error` to special-case
+    /// `NotFound`.
+    pub fn find_cause<E: Error + 'static>(&self) -> Option<&E> {
+        self.chain().find_map(|error| error.downcast_ref())
+    }
 }
 impl Error for Report<'static> {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
@@ -130,17 +375,88 @@ impl fmt::Debug for Report<'_> {
 }
 impl fmt::Display for Report<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let output = self.style.display(self.source.as_ref())?;
-
         writeln!(f, "`{}` reported an error", self.operation)?;
-        writeln!(f, "{output}")?;
+        self.style.display_into(self.source.as_ref(), f)?;
+        if let Some(snippet) = &self.snippet {
+            write!(f, "{snippet}")?;
+        }
+        writeln!(f)?;
 
         Ok(())
     }
 }
 
+/// A line of source code an error is about, such as the line of a file that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Snippet {
+    /// The path to the file.
+    pub path: PathBuf,
+    /// The line number (not index).
+    pub line: usize,
+    /// The column number (not index).
+    pub column: usize,
+    /// The source line.
+    pub source: String,
+    /// The range of characters in the source line to underline.
+    pub span: Range<usize>,
+}
+impl Snippet {
+    /// Create a snippet underlining the character at the column of the source line.
+    pub fn new<P: Into<PathBuf>, S: ToString>(
+        path: P,
+        line: usize,
+        column: usize,
+        source: S,
+    ) -> Self {
+        let start = column.saturating_sub(1);
+        Self {
+            path: path.into(),
+            line,
+            column,
+            source: source.to_string(),
+            span: start..start + 1,
+        }
+    }
+
+    /// Underline the range of characters in the source line instead of the column.
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = span;
+        self
+    }
+}
+/// Snippets are rendered like `rustc`'s diagnostics, with the file location, the source line, and
+/// the span underlined.
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = self.line.to_string();
+        let indent = " ".repeat(line.len());
+        let padding = " ".repeat(self.span.start);
+        let carets = "^".repeat(self.span.len().max(1));
+
+        writeln!(
+            f,
+            "{indent}{BOLD}{CYAN}--> {RESET}{}:{}:{}",
+            self.path.to_string_lossy(),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{indent} {BOLD}{CYAN}|{RESET}")?;
+        writeln!(f, "{BOLD}{CYAN}{line} |{RESET} {}", self.source)?;
+        writeln!(
+            f,
+            "{indent} {BOLD}{CYAN}|{RESET} {padding}{BOLD}{}{carets}{RESET}",
+            colours().error
+        )
+    }
+}
+
 /// Alias for a closure to format an error.
-pub type FmtErrorClosure<'a> = Box<dyn Fn(&mut String, usize, &dyn Error) -> fmt::Result + 'a>;
+///
+/// The closure is called with the output, the error's one based index in the chain, the number of
+/// errors in the chain, and the error.
+pub type FmtErrorClosure<'a> =
+    Box<dyn Fn(&mut dyn Write, usize, usize, &dyn Error) -> fmt::Result + 'a>;
 
 /// An error stack style.
 pub enum ErrorStackStyle<'a> {
@@ -164,29 +480,32 @@ impl ErrorStackStyle<'_> {
     /// Display an error in the given style.
     pub fn display(&self, source: &dyn Error) -> Result<String, fmt::Error> {
         let mut output = String::new();
+        self.display_into(source, &mut output)?;
+        Ok(output)
+    }
 
+    /// Display an error in the given style into an existing output.
+    pub fn display_into(&self, source: &dyn Error, output: &mut impl Write) -> fmt::Result {
         let fmt_fn = self.fmt_fn();
 
-        let mut current_error = Some(source);
-        let mut index = 1;
-        while let Some(error) = current_error {
-            fmt_fn(&mut output, index, error)?;
-            current_error = error.source();
-            index += 1;
+        let total = chain(source).count();
+        for (index, error) in chain(source).enumerate() {
+            fmt_fn(output, index + 1, total, error)?;
         }
 
-        Ok(output)
+        Ok(())
     }
 
     fn fmt_fn(&self) -> FmtErrorClosure<'_> {
         match &self {
-            Self::Inline => Box::new(|f, i, e| write!(f, " ----- {i}. {e}")),
+            Self::Inline => Box::new(|f, i, _, e| write!(f, " ----- {i}. {e}")),
 
-            Self::Stacked { indent } => Box::new(|f, i, e| {
+            Self::Stacked { indent } => Box::new(|f, i, _, e| {
                 writeln!(
                     f,
-                    "{}{BOLD}{RED}{i}{RESET}{BOLD}.{RESET} {e}",
-                    " ".repeat(*indent)
+                    "{}{BOLD}{}{i}{RESET}{BOLD}.{RESET} {e}",
+                    " ".repeat(*indent),
+                    colours().error
                 )
             }),
 
@@ -194,3 +513,54 @@ impl ErrorStackStyle<'_> {
         }
     }
 }
+
+/// A rendered report.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StringReport(String);
+impl StringReport {
+    /// The rendered report.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl fmt::Debug for StringReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+impl fmt::Display for StringReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl Error for StringReport {}
+
+/// An error rendered to its message, keeping the rendered chain of sources.
+#[derive(Debug, Clone, Default)]
+struct StringError {
+    message: String,
+    source: Option<Box<Self>>,
+}
+impl fmt::Display for StringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+impl Error for StringError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_ref()),
+            None => None,
+        }
+    }
+}
+
+/// Iterate over an error and its sources.
+fn chain<'e, 'a>(error: &'e (dyn Error + 'a)) -> impl Iterator<Item = &'e (dyn Error + 'a)> {
+    let mut current_error = Some(error);
+    iter::from_fn(move || {
+        let error = current_error?;
+        current_error = error.source();
+        Some(error)
+    })
+}
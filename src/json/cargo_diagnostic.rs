@@ -0,0 +1,75 @@
+use serde_json::{Value, json};
+
+use crate::{
+    json::{ValidationProblem, location::LocationExtensions, problem_messages::ProblemMessage},
+    style::strip_ansi,
+};
+
+impl ValidationProblem {
+    /// Render the problem as a rustc JSON diagnostic, the `message` of a `compiler-message` from
+    /// `cargo --message-format=json`, for tools that understand Rust diagnostics.
+    ///
+    /// The problem has a primary span if it has a position, spanning the whole value at the
+    /// instance path. Notes and the documentation URL are child diagnostics.
+    pub fn to_cargo_diagnostic(&self) -> Value {
+        let message = format!(
+            "`{}` {}",
+            self.instance_path.pointing_at(),
+            self.kind.headline()
+        );
+
+        let mut spans = Vec::new();
+        if let Some(location) = &self.location
+            && let Some(start) = location.position
+        {
+            let end = location.end.unwrap_or(start);
+
+            // Values end with an ASCII character in valid JSON, such as a closing brace, so the
+            // exclusive end is one byte and one column after the last character.
+            spans.push(json!({
+                "file_name": location.path.to_string_lossy(),
+                "byte_start": start.offset,
+                "byte_end": end.offset + 1,
+                "line_start": start.line,
+                "line_end": end.line,
+                "column_start": start.column,
+                "column_end": end.column + 1,
+                "is_primary": true,
+                "text": [],
+                "label": self.message(),
+                "suggested_replacement": null,
+                "suggestion_applicability": null,
+                "expansion": null,
+            }));
+        }
+
+        let mut children: Vec<_> = self.notes.iter().map(|note| child("note", note)).collect();
+        if let Some(docs_url) = &self.docs_url {
+            children.push(child(
+                "help",
+                &format!("for more information, see {docs_url}"),
+            ));
+        }
+
+        json!({
+            "$message_type": "diagnostic",
+            "message": message,
+            "code": { "code": self.code(), "explanation": null },
+            "level": self.severity.label(),
+            "spans": spans,
+            "children": children,
+            "rendered": strip_ansi(&self.to_string()),
+        })
+    }
+}
+
+fn child(level: &str, message: &str) -> Value {
+    json!({
+        "message": message,
+        "code": null,
+        "level": level,
+        "spans": [],
+        "children": [],
+        "rendered": null,
+    })
+}
@@ -0,0 +1,355 @@
+use core::{error::Error, fmt};
+use std::path::PathBuf;
+
+use jsonschema::Draft;
+use serde_json::{Map, Value};
+
+use crate::json::{Severity, ValidationProblem, escape_segment};
+
+/// Check the draft a schema declares with `$schema` matches the draft it will be validated with.
+///
+/// [`jsonschema::ValidationOptions`] detect the draft from `$schema` unless one is set with
+/// `with_draft`, in which case keywords from the declared draft may be silently ignored. Callers
+/// that set a draft should check it with this, loading a config warns about a mismatch. Schemas
+/// without `$schema` are always accepted.
+pub fn check_draft(schema: &Value, draft: Draft) -> Result<(), DraftMismatch> {
+    let Some(declared) = schema.get("$schema").and_then(Value::as_str) else {
+        return Ok(());
+    };
+
+    match draft.detect(schema) {
+        Ok(declared_draft) if declared_draft == draft => Ok(()),
+        _ => Err(DraftMismatch {
+            declared: declared.to_string(),
+            expected: draft,
+        }),
+    }
+}
+
+/// The `$schema` URI for a draft.
+pub(crate) fn draft_uri(draft: Draft) -> &'static str {
+    match draft {
+        Draft::Draft4 => "http://json-schema.org/draft-04/schema#",
+        Draft::Draft6 => "http://json-schema.org/draft-06/schema#",
+        Draft::Draft7 => "http://json-schema.org/draft-07/schema#",
+        Draft::Draft201909 => "https://json-schema.org/draft/2019-09/schema",
+        _ => "https://json-schema.org/draft/2020-12/schema",
+    }
+}
+
+/// A schema declares a different draft than it will be validated with.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub struct DraftMismatch {
+    pub declared: String,
+    pub expected: Draft,
+}
+impl fmt::Display for DraftMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the schema declares `{}` but will be validated as `{}`",
+            self.declared,
+            draft_uri(self.expected)
+        )
+    }
+}
+impl Error for DraftMismatch {}
+impl DraftMismatch {
+    /// The mismatch as a warning for the document validated against the schema.
+    pub(crate) fn to_problem(&self, file_path: Option<PathBuf>) -> ValidationProblem {
+        let mut problem = ValidationProblem::from_message(self, file_path);
+        problem.severity = Severity::Warning;
+        problem
+    }
+}
+
+/// The keywords whose values are maps of subschemas.
+const SCHEMA_MAP_KEYWORDS: [&str; 5] = [
+    "properties",
+    "patternProperties",
+    "definitions",
+    "$defs",
+    "dependentSchemas",
+];
+/// The keywords whose values are a subschema.
+const SCHEMA_KEYWORDS: [&str; 12] = [
+    "items",
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "propertyNames",
+    "if",
+    "then",
+    "else",
+    "not",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "dependencies",
+];
+/// The keywords whose values are arrays of subschemas.
+const SCHEMA_ARRAY_KEYWORDS: [&str; 5] = ["items", "prefixItems", "allOf", "anyOf", "oneOf"];
+/// The keywords that can't be translated to drafts outside of the range of drafts that support
+/// them.
+const UNTRANSLATABLE_KEYWORDS: [(&str, Draft, Draft); 15] = [
+    ("const", Draft::Draft6, Draft::Draft202012),
+    ("contains", Draft::Draft6, Draft::Draft202012),
+    ("propertyNames", Draft::Draft6, Draft::Draft202012),
+    ("if", Draft::Draft7, Draft::Draft202012),
+    ("then", Draft::Draft7, Draft::Draft202012),
+    ("else", Draft::Draft7, Draft::Draft202012),
+    (
+        "unevaluatedProperties",
+        Draft::Draft201909,
+        Draft::Draft202012,
+    ),
+    ("unevaluatedItems", Draft::Draft201909, Draft::Draft202012),
+    ("minContains", Draft::Draft201909, Draft::Draft202012),
+    ("maxContains", Draft::Draft201909, Draft::Draft202012),
+    ("$anchor", Draft::Draft201909, Draft::Draft202012),
+    ("$recursiveRef", Draft::Draft201909, Draft::Draft201909),
+    ("$recursiveAnchor", Draft::Draft201909, Draft::Draft201909),
+    ("$dynamicRef", Draft::Draft202012, Draft::Draft202012),
+    ("$dynamicAnchor", Draft::Draft202012, Draft::Draft202012),
+];
+
+/// Convert a schema to another draft, setting `$schema` to the draft's URI.
+///
+/// The schema's draft is detected from `$schema`, defaulting to the latest draft. Keywords that
+/// were renamed or reshaped between drafts are translated: `definitions` and `$defs` (and local
+/// `$ref`s into them), `id` and `$id`, the boolean and numeric forms of `exclusiveMinimum` and
+/// `exclusiveMaximum`, the array form of `items` and `prefixItems`, and `dependencies` and
+/// `dependentRequired`/`dependentSchemas`. Other keywords the draft does not support are kept and
+/// reported, validators for the draft will ignore them.
+pub fn convert_draft(schema: &Value, draft: Draft) -> DraftConversion {
+    let from = Draft::default().detect(schema).unwrap_or_default();
+
+    let mut conversion = DraftConversion {
+        schema: schema.clone(),
+        untranslated: Vec::new(),
+    };
+    convert(
+        &mut conversion.schema,
+        String::new(),
+        from,
+        draft,
+        &mut conversion.untranslated,
+    );
+
+    if let Some(root) = conversion.schema.as_object_mut() {
+        root.insert(
+            "$schema".to_string(),
+            Value::String(draft_uri(draft).to_string()),
+        );
+    }
+
+    conversion
+}
+
+/// A schema converted to another draft, see [`convert_draft`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DraftConversion {
+    /// The converted schema.
+    pub schema: Value,
+    /// The keywords that could not be translated to the draft.
+    pub untranslated: Vec<UntranslatedKeyword>,
+}
+
+/// A keyword that could not be translated to a draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UntranslatedKeyword {
+    /// The JSON pointer to the subschema containing the keyword.
+    pub pointer: String,
+    /// The keyword.
+    pub keyword: String,
+    /// The draft the keyword could not be translated to.
+    pub draft: Draft,
+}
+impl fmt::Display for UntranslatedKeyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "[root]"
+        } else {
+            &self.pointer
+        };
+
+        write!(
+            f,
+            "`{pointer}` uses `{}` which `{}` does not support",
+            self.keyword,
+            draft_uri(self.draft)
+        )
+    }
+}
+
+fn convert(
+    node: &mut Value,
+    pointer: String,
+    from: Draft,
+    to: Draft,
+    untranslated: &mut Vec<UntranslatedKeyword>,
+) {
+    let Some(object) = node.as_object_mut() else {
+        return;
+    };
+
+    let mut untranslatable = |keyword: &str| {
+        untranslated.push(UntranslatedKeyword {
+            pointer: pointer.clone(),
+            keyword: keyword.to_string(),
+            draft: to,
+        });
+    };
+
+    // Identifiers
+    if from == Draft::Draft4 && to != Draft::Draft4 {
+        rename(object, "id", "$id", Value::is_string);
+    } else if from != Draft::Draft4 && to == Draft::Draft4 {
+        rename(object, "$id", "id", Value::is_string);
+    }
+
+    // Definitions
+    if to >= Draft::Draft201909 {
+        rename(object, "definitions", "$defs", Value::is_object);
+    } else {
+        rename(object, "$defs", "definitions", Value::is_object);
+    }
+    if let Some(Value::String(reference)) = object.get_mut("$ref")
+        && reference.starts_with('#')
+    {
+        *reference = if to >= Draft::Draft201909 {
+            reference.replace("/definitions/", "/$defs/")
+        } else {
+            reference.replace("/$defs/", "/definitions/")
+        };
+    }
+
+    // Exclusive bounds
+    for (exclusive, bound) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        match object.get(exclusive).cloned() {
+            Some(Value::Bool(is_exclusive)) if to != Draft::Draft4 => {
+                object.remove(exclusive);
+                if is_exclusive && let Some(bound) = object.remove(bound) {
+                    object.insert(exclusive.to_string(), bound);
+                }
+            }
+            Some(value @ Value::Number(_)) if to == Draft::Draft4 => {
+                if object.contains_key(bound) {
+                    untranslatable(exclusive);
+                } else {
+                    object.insert(bound.to_string(), value);
+                    object.insert(exclusive.to_string(), Value::Bool(true));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Tuples
+    if to == Draft::Draft202012 {
+        if object.get("items").is_some_and(Value::is_array) {
+            rename(object, "items", "prefixItems", Value::is_array);
+            rename(object, "additionalItems", "items", |_| true);
+        }
+    } else if object.contains_key("prefixItems") {
+        if let Some(items) = object.remove("items") {
+            object.insert("additionalItems".to_string(), items);
+        }
+        rename(object, "prefixItems", "items", Value::is_array);
+    }
+
+    // Dependencies
+    if to >= Draft::Draft201909 {
+        if let Some(Value::Object(dependencies)) = object.remove("dependencies") {
+            for (property, dependency) in dependencies {
+                let keyword = if dependency.is_array() {
+                    "dependentRequired"
+                } else {
+                    "dependentSchemas"
+                };
+                if let Value::Object(dependents) = object
+                    .entry(keyword)
+                    .or_insert_with(|| Value::Object(Map::new()))
+                {
+                    dependents.insert(property, dependency);
+                }
+            }
+        }
+    } else {
+        for keyword in ["dependentRequired", "dependentSchemas"] {
+            if let Some(Value::Object(dependents)) = object.remove(keyword)
+                && let Value::Object(dependencies) = object
+                    .entry("dependencies")
+                    .or_insert_with(|| Value::Object(Map::new()))
+            {
+                dependencies.extend(dependents);
+            }
+        }
+    }
+
+    // Unsupported keywords
+    for (keyword, ..) in UNTRANSLATABLE_KEYWORDS
+        .iter()
+        .filter(|(keyword, earliest, latest)| {
+            object.contains_key(*keyword) && (to < *earliest || to > *latest)
+        })
+    {
+        untranslatable(keyword);
+    }
+
+    // Subschemas
+    for keyword in SCHEMA_MAP_KEYWORDS {
+        if let Some(Value::Object(children)) = object.get_mut(keyword) {
+            for (key, child) in children {
+                let child_pointer = format!("{pointer}/{keyword}/{}", escape_segment(key));
+                convert(child, child_pointer, from, to, untranslated);
+            }
+        }
+    }
+    for keyword in SCHEMA_KEYWORDS {
+        match object.get_mut(keyword) {
+            // `dependencies` is a map of subschemas and arrays of property names.
+            Some(Value::Object(children)) if keyword == "dependencies" => {
+                for (key, child) in children {
+                    let child_pointer = format!("{pointer}/{keyword}/{}", escape_segment(key));
+                    convert(child, child_pointer, from, to, untranslated);
+                }
+            }
+            Some(child @ Value::Object(_)) => {
+                convert(
+                    child,
+                    format!("{pointer}/{keyword}"),
+                    from,
+                    to,
+                    untranslated,
+                );
+            }
+            _ => {}
+        }
+    }
+    for keyword in SCHEMA_ARRAY_KEYWORDS {
+        if let Some(Value::Array(children)) = object.get_mut(keyword) {
+            for (index, child) in children.iter_mut().enumerate() {
+                let child_pointer = format!("{pointer}/{keyword}/{index}");
+                convert(child, child_pointer, from, to, untranslated);
+            }
+        }
+    }
+}
+
+/// Rename a keyword if the value matches and the new keyword is not already present.
+fn rename(object: &mut Map<String, Value>, from: &str, to: &str, matches: fn(&Value) -> bool) {
+    if object.contains_key(to) || !object.get(from).is_some_and(matches) {
+        return;
+    }
+
+    if let Some(value) = object.remove(from) {
+        object.insert(to.to_string(), value);
+    }
+}
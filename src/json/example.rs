@@ -0,0 +1,162 @@
+use serde_json::{Map, Value};
+
+/// The maximum depth of subschemas to follow, so recursive schemas produce a finite example.
+const MAX_DEPTH: usize = 32;
+
+/// Build an example instance from the schema, such as a fully populated config for onboarding.
+///
+/// Each value uses the first of the schema's `examples`, then its `default`, `const`, or first
+/// `enum` value. Otherwise a placeholder for the schema's type is used, objects have every
+/// property, arrays have a single item, and numbers and strings respect `minimum` and
+/// `minLength`. Local `$ref`s are followed, as is the first subschema of a `oneOf` or `anyOf`,
+/// and the objects from `allOf` are merged.
+///
+/// The example is not guaranteed to be valid, such as with `pattern` or `not`.
+pub fn example(schema: &Value) -> Value {
+    example_at(schema, schema, 0)
+}
+
+fn example_at(root: &Value, schema: &Value, depth: usize) -> Value {
+    let Value::Object(keywords) = schema else {
+        return Value::Null;
+    };
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+
+    let first = |keyword: &str| {
+        keywords
+            .get(keyword)
+            .and_then(Value::as_array)
+            .and_then(|values| values.first())
+    };
+
+    if let Some(value) = first("examples")
+        .or_else(|| keywords.get("default"))
+        .or_else(|| keywords.get("const"))
+        .or_else(|| first("enum"))
+    {
+        return value.clone();
+    }
+
+    if let Some(reference) = keywords.get("$ref").and_then(Value::as_str)
+        && let Some(target) = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+    {
+        return example_at(root, target, depth + 1);
+    }
+
+    if let Some(subschema) = first("oneOf").or_else(|| first("anyOf")) {
+        return example_at(root, subschema, depth + 1);
+    }
+
+    let mut value = placeholder(root, keywords, depth);
+
+    for subschema in keywords
+        .get("allOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        match (&mut value, example_at(root, subschema, depth + 1)) {
+            (Value::Object(object), Value::Object(other)) => object.extend(other),
+            (value @ Value::Null, other) => *value = other,
+            _ => {}
+        }
+    }
+
+    value
+}
+
+/// A placeholder value for the schema's type, the first type that is not `null` if the schema
+/// allows many.
+fn placeholder(root: &Value, keywords: &Map<String, Value>, depth: usize) -> Value {
+    let kind = match keywords.get("type") {
+        Some(Value::String(kind)) => Some(kind.as_str()),
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|kind| *kind != "null"),
+        _ if keywords.contains_key("properties") => Some("object"),
+        _ if keywords.contains_key("items") || keywords.contains_key("prefixItems") => {
+            Some("array")
+        }
+        _ => None,
+    };
+
+    match kind {
+        Some("object") => Value::Object(
+            keywords
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| (name.clone(), example_at(root, property, depth + 1)))
+                .collect(),
+        ),
+        Some("array") => {
+            let mut items: Vec<Value> = keywords
+                .get("prefixItems")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|item| example_at(root, item, depth + 1))
+                .collect();
+            if items.is_empty()
+                && let Some(item @ Value::Object(_)) = keywords.get("items")
+            {
+                items.push(example_at(root, item, depth + 1));
+            }
+            Value::Array(items)
+        }
+        Some("string") => Value::String(string_placeholder(keywords)),
+        Some("integer") => keywords
+            .get("minimum")
+            .and_then(Value::as_i64)
+            .or_else(|| {
+                keywords
+                    .get("exclusiveMinimum")
+                    .and_then(Value::as_i64)
+                    .map(|minimum| minimum.saturating_add(1))
+            })
+            .unwrap_or(0)
+            .into(),
+        Some("number") => keywords
+            .get("minimum")
+            .or_else(|| keywords.get("exclusiveMinimum"))
+            .filter(|minimum| minimum.is_number())
+            .cloned()
+            .unwrap_or_else(|| 0.0.into()),
+        Some("boolean") => Value::Bool(false),
+        _ => Value::Null,
+    }
+}
+
+/// A placeholder string for the schema's `format`, or a string of `minLength`.
+fn string_placeholder(keywords: &Map<String, Value>) -> String {
+    let placeholder = match keywords.get("format").and_then(Value::as_str) {
+        Some("date-time") => "1970-01-01T00:00:00Z",
+        Some("date") => "1970-01-01",
+        Some("time") => "00:00:00Z",
+        Some("email") => "user@example.com",
+        Some("hostname") => "example.com",
+        Some("ipv4") => "127.0.0.1",
+        Some("ipv6") => "::1",
+        Some("uri" | "url") => "https://example.com",
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        _ => "",
+    };
+
+    let min_length = keywords
+        .get("minLength")
+        .and_then(Value::as_u64)
+        .and_then(|length| usize::try_from(length).ok())
+        .unwrap_or(0);
+
+    if placeholder.chars().count() >= min_length {
+        placeholder.to_string()
+    } else {
+        "x".repeat(min_length)
+    }
+}
@@ -0,0 +1,66 @@
+use core::fmt::Write;
+
+use crate::json::{
+    ValidationErrors, location::LocationExtensions, problem_messages::ProblemMessage,
+};
+
+impl ValidationErrors {
+    /// Render the problems as GitHub Actions workflow commands, one `::error` or `::warning`
+    /// command per problem, so they are shown as annotations on pull requests.
+    pub fn to_github_annotations(&self) -> String {
+        let mut output = String::new();
+
+        for problem in &self.problems {
+            let mut properties = Vec::new();
+
+            let path = problem
+                .location
+                .as_ref()
+                .map(|location| &location.path)
+                .or(self.file_path.as_ref());
+            if let Some(path) = path {
+                properties.push(format!("file={}", escape_property(&path.to_string_lossy())));
+            }
+
+            if let Some(position) = problem
+                .location
+                .as_ref()
+                .and_then(|location| location.position)
+            {
+                properties.push(format!("line={}", position.line));
+                properties.push(format!("col={}", position.column));
+            }
+
+            let title = format!(
+                "`{}` {}",
+                problem.instance_path.pointing_at(),
+                problem.kind.headline()
+            );
+            let message = problem.message().unwrap_or_else(|| title.clone());
+            properties.push(format!("title={}", escape_property(&title)));
+
+            let _ = writeln!(
+                output,
+                "::{} {}::{}",
+                problem.severity,
+                properties.join(","),
+                escape_data(&message)
+            );
+        }
+
+        output
+    }
+}
+
+/// Escape a workflow command's message.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command's property value.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
@@ -0,0 +1,76 @@
+use core::fmt::Write;
+
+use serde_json::Value;
+
+use crate::style::{BLUE, GREEN, MAGENTA, RESET, YELLOW};
+
+/// The indent for each level of nesting.
+const INDENT: &str = "  ";
+
+/// Pretty print a JSON value with object keys, strings, numbers, booleans, and nulls in different
+/// colors, for dumping a value to the terminal.
+///
+/// Without the `styled` feature the output is the same as [`serde_json::to_string_pretty`].
+pub fn highlight(value: &Value) -> String {
+    let mut output = String::new();
+    write_value(&mut output, value, 0);
+    output
+}
+
+fn write_value(output: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Null => {
+            let _ = write!(output, "{MAGENTA}null{RESET}");
+        }
+        Value::Bool(value) => {
+            let _ = write!(output, "{MAGENTA}{value}{RESET}");
+        }
+        Value::Number(value) => {
+            let _ = write!(output, "{YELLOW}{value}{RESET}");
+        }
+        Value::String(_) => {
+            let _ = write!(output, "{GREEN}{value}{RESET}");
+        }
+        Value::Array(items) => {
+            if items.is_empty() {
+                output.push_str("[]");
+                return;
+            }
+
+            output.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                newline(output, depth + 1);
+                write_value(output, item, depth + 1);
+            }
+            newline(output, depth);
+            output.push(']');
+        }
+        Value::Object(properties) => {
+            if properties.is_empty() {
+                output.push_str("{}");
+                return;
+            }
+
+            output.push('{');
+            for (index, (key, property)) in properties.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                newline(output, depth + 1);
+                let key = Value::String(key.clone());
+                let _ = write!(output, "{BLUE}{key}{RESET}: ");
+                write_value(output, property, depth + 1);
+            }
+            newline(output, depth);
+            output.push('}');
+        }
+    }
+}
+
+fn newline(output: &mut String, depth: usize) {
+    output.push('\n');
+    output.push_str(&INDENT.repeat(depth));
+}
@@ -0,0 +1,141 @@
+use core::fmt::Write;
+
+use crate::json::{
+    ProblemStyle, ValidationErrors, ValidationProblem, location::LocationExtensions,
+    problem_messages::ProblemMessage,
+};
+
+impl ValidationErrors {
+    /// Render the problems as HTML, see [`ValidationProblem::to_html`].
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&ProblemStyle::default())
+    }
+
+    /// Render the problems as HTML using the style's characters, see
+    /// [`ValidationProblem::to_html_with`].
+    pub fn to_html_with(&self, style: &ProblemStyle) -> String {
+        self.problems
+            .iter()
+            .map(|problem| problem.to_html_with(style))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl ValidationProblem {
+    /// Render the problem as HTML with the same layout as the terminal output.
+    ///
+    /// The problem is wrapped in a `<pre class="problem">` so the underline aligns in a monospace
+    /// font, and each part is wrapped in a `<span>` with a class for styling: `error` or `warning`,
+    /// `headline`, `gutter`, `file`, `source`, `caret`, `message`, `note`, and `link`.
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&ProblemStyle::default())
+    }
+
+    /// Render the problem as HTML using the style's characters for the markers, see
+    /// [`ValidationProblem::to_html`].
+    ///
+    /// The style's colours are not used, the HTML is styled by its classes.
+    pub fn to_html_with(&self, style: &ProblemStyle) -> String {
+        let mut output = String::new();
+
+        let chars = &style.chars;
+        let indent = " ".repeat(self.indent());
+        let gutter = format!(
+            r#"{indent}<span class="gutter"> {} </span>"#,
+            escape(chars.gutter)
+        );
+        let note_marker = format!(
+            r#"{indent}<span class="gutter"> {} </span>"#,
+            escape(chars.note)
+        );
+        let severity = self.severity;
+
+        let _ = writeln!(
+            output,
+            r#"<pre class="problem"><span class="{severity}">{severity}[{}]</span>: <span class="headline">`{}` {}</span>"#,
+            self.code(),
+            escape(&self.instance_path.pointing_at()),
+            escape(&self.kind.headline()),
+        );
+
+        if let Some(location) = &self.location {
+            let mut file = location.path.to_string_lossy().to_string();
+            if let Some(position) = location.position {
+                let _ = write!(file, ":{}:{}", position.line, position.column);
+            }
+
+            let _ = writeln!(
+                output,
+                r#"{indent}<span class="gutter">{} </span><span class="file">{}</span>"#,
+                escape(chars.arrow),
+                escape(&file)
+            );
+        }
+
+        let line = self
+            .location
+            .as_ref()
+            .and_then(|location| location.position)
+            .map_or_else(String::new, |position| position.line.to_string());
+        let width = self.indent();
+
+        let _ = writeln!(output, "{gutter}");
+        let _ = writeln!(
+            output,
+            r#"<span class="gutter">{line:>width$} {} </span><span class="source">{}</span>"#,
+            escape(chars.gutter),
+            escape(&self.source)
+        );
+
+        let range = self.char_range();
+        let _ = write!(
+            output,
+            r#"{gutter}{}<span class="caret">{}</span>"#,
+            " ".repeat(range.start),
+            escape(&chars.caret.repeat(range.len().max(1)))
+        );
+        if let Some(message) = self.message() {
+            let _ = write!(
+                output,
+                r#" <span class="message">{}</span>"#,
+                escape(&message)
+            );
+        }
+        let _ = writeln!(output);
+
+        if !self.notes.is_empty() || self.docs_url.is_some() {
+            let _ = writeln!(output, "{gutter}");
+
+            for note in &self.notes {
+                let _ = writeln!(
+                    output,
+                    r#"{note_marker}<span class="note">note:</span> {}"#,
+                    escape(note)
+                );
+            }
+
+            if let Some(url) = &self.docs_url {
+                let url = escape(url);
+                let _ = writeln!(
+                    output,
+                    r#"{note_marker}<span class="note">see:</span> <a class="link" href="{url}">{url}</a>"#,
+                );
+            }
+        }
+
+        output.push_str("</pre>");
+
+        output
+    }
+}
+
+/// Escape the HTML special characters.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
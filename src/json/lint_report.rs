@@ -0,0 +1,152 @@
+use core::{error::Error, fmt};
+
+use crate::{
+    json::{Severity, ValidationErrors},
+    style::{BOLD, DIM, RESET, colours},
+};
+
+/// The problems from linting many JSON documents, one set of problems per document.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct LintReport {
+    /// The problems for each linted document, including documents without problems.
+    pub files: Vec<ValidationErrors>,
+    /// Whether documents with only warnings failed, as when linting with `--strict`.
+    pub strict: bool,
+}
+impl LintReport {
+    /// Create a report from the problems for each linted document.
+    pub fn new(files: Vec<ValidationErrors>) -> Self {
+        Self {
+            files,
+            strict: false,
+        }
+    }
+
+    /// Fail documents with only warnings.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Add the problems for a linted document.
+    pub fn push(&mut self, file: ValidationErrors) {
+        self.files.push(file);
+    }
+
+    /// If no document has any problems.
+    pub fn is_empty(&self) -> bool {
+        self.files.iter().all(|file| file.problems.is_empty())
+    }
+
+    /// The total number of problems across all documents.
+    pub fn total(&self) -> usize {
+        self.files.iter().map(|file| file.problems.len()).sum()
+    }
+
+    /// If any document has an error, warnings alone do not count.
+    pub fn has_errors(&self) -> bool {
+        self.files
+            .iter()
+            .any(|file| file.count(Severity::Error) > 0)
+    }
+
+    /// The number of documents that failed, those with an error, or with any problem if the
+    /// report is strict.
+    pub fn failed(&self) -> usize {
+        self.files.iter().filter(|file| self.fails(file)).count()
+    }
+
+    /// The number of documents that passed, including documents with only warnings unless the
+    /// report is strict.
+    pub fn passed(&self) -> usize {
+        self.files.len() - self.failed()
+    }
+
+    fn fails(&self, file: &ValidationErrors) -> bool {
+        file.problems
+            .iter()
+            .any(|problem| self.strict || problem.severity == Severity::Error)
+    }
+}
+impl FromIterator<ValidationErrors> for LintReport {
+    fn from_iter<T: IntoIterator<Item = ValidationErrors>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let colours = colours();
+        let mut errors = 0;
+        let mut warnings = 0;
+        let mut suppressed = 0;
+
+        for file in &self.files {
+            let file_errors = file.count(Severity::Error);
+            let file_warnings = file.count(Severity::Warning);
+            errors += file_errors;
+            warnings += file_warnings;
+            suppressed += file.suppressed;
+
+            if file.problems.is_empty() {
+                writeln!(f, "{BOLD}{}ok{RESET} `{}`", colours.success, file.name())?;
+                continue;
+            }
+
+            let (status, colour) = if self.fails(file) {
+                ("failed", colours.error)
+            } else {
+                ("warned", colours.warning)
+            };
+            writeln!(f, "{BOLD}{colour}{status}{RESET} `{}`", file.name())?;
+            for problem in &file.problems {
+                writeln!(f, "{problem}")?;
+            }
+        }
+
+        let failed = self.failed();
+        let failed_colour = if failed > 0 {
+            colours.error
+        } else {
+            colours.success
+        };
+        write!(
+            f,
+            "{BOLD}checked {}{RESET}, {BOLD}{}{} passed{RESET}, {BOLD}{failed_colour}{failed} failed{RESET}",
+            plural(self.files.len(), "file"),
+            colours.success,
+            self.passed(),
+        )?;
+        if errors > 0 {
+            write!(
+                f,
+                ", {BOLD}{}{}{RESET}",
+                colours.error,
+                plural(errors, "error")
+            )?;
+        }
+        if warnings > 0 {
+            write!(
+                f,
+                ", {BOLD}{}{}{RESET}",
+                colours.warning,
+                plural(warnings, "warning")
+            )?;
+        }
+        if suppressed > 0 {
+            write!(f, ", {DIM}{suppressed} suppressed{RESET}")?;
+        }
+
+        Ok(())
+    }
+}
+impl Error for LintReport {}
+
+/// The count followed by the noun, pluralised unless the count is one.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
@@ -14,6 +14,10 @@ pub trait LocationExtensions: Sized {
 
     /// Try reconstruct the value
     fn reconstruct(&self, value: &Value) -> String;
+
+    /// Return the location as an RFC 6901 JSON pointer, such as `/foo/0/bar`, with `~` and `/` in
+    /// properties escaped as `~0` and `~1`. The root is the empty string.
+    fn to_json_pointer(&self) -> String;
 }
 
 impl LocationExtensions for Location {
@@ -58,4 +62,14 @@ impl LocationExtensions for Location {
 
         format!("{key}{value}")
     }
+
+    fn to_json_pointer(&self) -> String {
+        // The location stores it's segments escaped.
+        self.as_str().to_string()
+    }
+}
+
+/// Escape a JSON pointer segment, `~` and `/` are escaped as `~0` and `~1`.
+pub(crate) fn escape_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
 }
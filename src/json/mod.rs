@@ -1,22 +1,52 @@
 //! Helpers for working with JSON
 
+mod cargo_diagnostic;
+mod draft;
+mod example;
+mod github;
+mod highlight;
+mod html;
+mod lint_report;
 mod location;
 mod positioned_parser;
 mod problem;
 mod problem_messages;
+mod problem_style;
+mod sarif;
+mod serialize;
+mod subschema;
+mod suggestion;
+mod unknown_properties;
 
-pub use problem::ValidationProblem;
+pub use example::example;
+pub use highlight::highlight;
+pub use lint_report::LintReport;
+pub use problem::{Severity, ValidationProblem};
+pub use problem_style::{DiagnosticChars, ProblemStyle};
 
 use core::{
     error::Error,
     fmt::{self, Debug},
 };
-use std::{borrow::Cow, path::PathBuf};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+};
 
-use jsonschema::ValidationOptions;
+use jsonschema::{
+    ValidationError, ValidationOptions, Validator,
+    paths::{Location, LocationSegment},
+};
 use serde_json::Value;
 
-pub use positioned_parser::{Position, PositionedJsonNode};
+pub use draft::{DraftConversion, DraftMismatch, UntranslatedKeyword, check_draft, convert_draft};
+pub use location::LocationExtensions;
+pub(crate) use location::escape_segment;
+pub use positioned_parser::{ParseStats, Position, PositionedJsonNode};
+pub use subschema::validate_at;
+pub use suggestion::{Edit, Suggestion, apply_suggestions};
+pub use unknown_properties::unknown_properties;
 
 /// Validate a JSON instance against a JSON schema.
 pub fn validate(
@@ -26,49 +56,318 @@ pub fn validate(
     document: Option<&PositionedJsonNode>,
     file_path: Option<PathBuf>,
 ) -> Result<(), ValidationErrors> {
-    let validator = validation_options
-        .build(schema)
+    let validator = build_validator(schema, &validation_options)
         .expect("JSON schema must be able to create a validator");
 
-    if !validator.is_valid(instance) {
-        let mut problems = Vec::new();
-        for error in validator.iter_errors(instance) {
-            problems.push(ValidationProblem::new(
-                error,
-                schema,
-                document,
-                file_path.clone(),
-            ));
-        }
-
+    let problems = problems(&validator, schema, instance, document, file_path.as_ref());
+    if !problems.is_empty() {
         return Err(ValidationErrors {
             file_path,
+            label: None,
             problems,
+            suppressed: 0,
         });
     }
 
     Ok(())
 }
 
+/// Validate a JSON instance against a JSON schema, also warning if the schema declares a different
+/// draft with `$schema` than it is validated with, see [`check_draft`].
+pub(crate) fn validate_checking_draft(
+    schema: &Value,
+    instance: &Value,
+    validation_options: ValidationOptions,
+    document: Option<&PositionedJsonNode>,
+    file_path: Option<&PathBuf>,
+) -> Vec<ValidationProblem> {
+    let validator = build_validator(schema, &validation_options)
+        .expect("JSON schema must be able to create a validator");
+
+    let mut problems = problems(&validator, schema, instance, document, file_path);
+    if let Err(mismatch) = check_draft(schema, validator.draft()) {
+        problems.push(mismatch.to_problem(file_path.cloned()));
+    }
+
+    problems
+}
+
+/// The problems with the instance, empty if it is valid.
+fn problems(
+    validator: &Validator,
+    schema: &Value,
+    instance: &Value,
+    document: Option<&PositionedJsonNode>,
+    file_path: Option<&PathBuf>,
+) -> Vec<ValidationProblem> {
+    if validator.is_valid(instance) {
+        return Vec::new();
+    }
+
+    validator
+        .iter_errors(instance)
+        .map(|error| ValidationProblem::new(error, schema, document, file_path.cloned()))
+        .collect()
+}
+
+/// Check if a JSON instance is valid against a JSON schema, without building any problems.
+///
+/// This short-circuits on the first failure so is cheaper than [`validate`] when only the
+/// validity is needed.
+pub fn is_valid(
+    schema: &Value,
+    instance: &Value,
+    validation_options: ValidationOptions,
+) -> Result<bool, SchemaError> {
+    let validator = build_validator(schema, &validation_options)?;
+    Ok(validator.is_valid(instance))
+}
+
+/// Validation options that assert `format`, so values that don't match a known format such as
+/// `email` or `uri` are invalid, for documents where formats are constraints rather than
+/// annotations.
+///
+/// From draft 2019-09 formats are only annotations by default. Unknown formats, such as the
+/// `uint64` that `schemars` generates, are still ignored. The draft is detected from the schema's
+/// `$schema`, defaulting to draft 2020-12, as setting a draft can silently ignore keywords, see
+/// [`check_draft`].
+///
+/// Configs can be validated with these options by returning them from
+/// `ConfigFile::validation_options`, which defaults to only annotating formats.
+pub fn strict_options() -> ValidationOptions {
+    ValidationOptions::default()
+        .should_validate_formats(true)
+        .should_ignore_unknown_formats(true)
+}
+
+/// Check a JSON schema can be used to create a validator.
+pub fn check_schema(
+    schema: &Value,
+    validation_options: ValidationOptions,
+) -> Result<(), SchemaError> {
+    build_validator(schema, &validation_options).map(|_| ())
+}
+
+/// Try build a validator for the schema.
+fn build_validator(
+    schema: &Value,
+    validation_options: &ValidationOptions,
+) -> Result<Validator, SchemaError> {
+    validation_options
+        .build(schema)
+        .map_err(|source| SchemaError {
+            source: Box::new(source),
+        })
+}
+
+/// A JSON schema could not be used to create a validator.
+#[derive(Debug)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub struct SchemaError {
+    pub source: Box<ValidationError<'static>>,
+}
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the JSON schema is invalid")
+    }
+}
+impl Error for SchemaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
 /// A set of problems with a JSON document.
 #[derive(Debug)]
 #[non_exhaustive]
 #[allow(missing_docs)]
 pub struct ValidationErrors {
     pub file_path: Option<PathBuf>,
+    pub label: Option<String>,
     pub problems: Vec<ValidationProblem>,
+    pub suppressed: usize,
+}
+impl ValidationErrors {
+    /// Set the label for the validated JSON, used instead of `JSON` when there is no file path.
+    pub fn with_label<S: ToString>(mut self, label: S) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Remove the problems with any of the codes, counting them as suppressed.
+    pub fn suppress<S: AsRef<str>>(&mut self, codes: &[S]) {
+        let count = self.problems.len();
+        self.problems
+            .retain(|problem| !codes.iter().any(|code| code.as_ref() == problem.code()));
+        self.suppressed += count - self.problems.len();
+    }
+
+    /// Remove problems that are identical to an earlier problem, such as the same property being
+    /// flagged by two schemas.
+    ///
+    /// Problems are identical when they are equal, they have the same file path, instance path,
+    /// severity, and message, the schema path is ignored as it differs between schemas.
+    pub fn dedupe(&mut self) {
+        let mut seen = HashSet::with_capacity(self.problems.len());
+        let is_unique: Vec<bool> = self
+            .problems
+            .iter()
+            .map(|problem| seen.insert(problem.identity()))
+            .collect();
+
+        let mut is_unique = is_unique.into_iter();
+        self.problems.retain(|_| is_unique.next().unwrap_or(true));
+    }
+
+    /// The name of the validated JSON, the file path, label, or `JSON`.
+    pub(crate) fn name(&self) -> Cow<'_, str> {
+        self.file_path.as_ref().map_or_else(
+            || Cow::Borrowed(self.label.as_deref().unwrap_or("JSON")),
+            |path| path.to_string_lossy(),
+        )
+    }
+
+    /// The number of problems with the severity.
+    pub(crate) fn count(&self, severity: Severity) -> usize {
+        self.problems
+            .iter()
+            .filter(|problem| problem.severity == severity)
+            .count()
+    }
+
+    /// Group the problems by their instance path as a JSON pointer, such as `/object/value`.
+    pub fn by_path(&self) -> BTreeMap<String, Vec<&ValidationProblem>> {
+        let mut problems: BTreeMap<String, Vec<&ValidationProblem>> = BTreeMap::new();
+        for problem in &self.problems {
+            problems
+                .entry(problem.instance_path.to_json_pointer())
+                .or_default()
+                .push(problem);
+        }
+
+        problems
+    }
+
+    /// Merge problems that differ only in the index of an array item into the first of them, with
+    /// a note listing the indices of every item with the problem.
+    ///
+    /// Problems are merged when they have the same severity, rule, message, and schema path, and
+    /// their instance paths differ only in the last array index. This shortens reports for arrays
+    /// where many items fail the same way.
+    ///
+    /// Configs are grouped when loaded with `LoadOptions::group_by_index`, such as by
+    /// `config lint --group-items`.
+    pub fn group_by_index(&mut self) {
+        let mut groups: HashMap<(Severity, String, Option<String>, String, String), usize> =
+            HashMap::new();
+        let mut indices: Vec<Vec<usize>> = Vec::new();
+        let mut problems: Vec<ValidationProblem> = Vec::new();
+
+        for problem in self.problems.drain(..) {
+            let segments: Vec<_> = problem.instance_path.into_iter().collect();
+            let last_index = segments
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(position, segment)| match segment {
+                    LocationSegment::Index(index) => Some((position, *index)),
+                    LocationSegment::Property(_) => None,
+                });
+            let Some((position, index)) = last_index else {
+                indices.push(Vec::new());
+                problems.push(problem);
+                continue;
+            };
+
+            let pattern = segments
+                .iter()
+                .enumerate()
+                .map(|(i, segment)| {
+                    if i == position {
+                        "*".to_string()
+                    } else {
+                        segment.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            let key = (
+                problem.severity,
+                problem.code().to_string(),
+                problem.message(),
+                problem.schema_path.to_json_pointer(),
+                pattern,
+            );
+
+            match groups.get(&key) {
+                Some(&group) => indices[group].push(index),
+                None => {
+                    groups.insert(key, problems.len());
+                    indices.push(vec![index]);
+                    problems.push(problem);
+                }
+            }
+        }
+
+        for (problem, indices) in problems.iter_mut().zip(indices) {
+            if indices.len() > 1 {
+                let indices: Vec<_> = indices.iter().map(usize::to_string).collect();
+                problem.notes.push(format!(
+                    "this applies to the items [{}]",
+                    indices.join(", ")
+                ));
+            }
+        }
+
+        self.problems = problems;
+    }
+
+    /// The problems in their canonical order, see [`ValidationProblem::sort_key`].
+    pub fn sorted(&self) -> Vec<&ValidationProblem> {
+        let mut problems: Vec<_> = self.problems.iter().collect();
+        problems.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        problems
+    }
+
+    /// Iterate over at most one problem per instance path, in the order the paths were first
+    /// reported.
+    ///
+    /// The most severe problem for each path is preferred, followed by the first reported.
+    pub fn first_per_path(&self) -> impl Iterator<Item = &ValidationProblem> {
+        let mut indices: HashMap<&Location, usize> = HashMap::new();
+        let mut chosen: Vec<&ValidationProblem> = Vec::new();
+
+        for problem in &self.problems {
+            match indices.get(&problem.instance_path) {
+                Some(&index) => {
+                    if problem.severity < chosen[index].severity {
+                        chosen[index] = problem;
+                    }
+                }
+                None => {
+                    indices.insert(&problem.instance_path, chosen.len());
+                    chosen.push(problem);
+                }
+            }
+        }
+
+        chosen.into_iter()
+    }
 }
 impl fmt::Display for ValidationErrors {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(
-            f,
-            "`{}` generated {} errors:",
-            self.file_path.as_ref().map_or_else(
-                || Cow::Owned("JSON".to_string()),
-                |path| path.to_string_lossy(),
-            ),
-            self.problems.len()
-        )?;
+        let warnings = self.count(Severity::Warning);
+        let errors = self.problems.len() - warnings;
+
+        write!(f, "`{}` generated {errors} errors", self.name())?;
+        if warnings > 0 {
+            write!(f, " and {warnings} warnings")?;
+        }
+        if self.suppressed > 0 {
+            write!(f, " ({} suppressed)", self.suppressed)?;
+        }
+        writeln!(f, ":")?;
 
         for problem in &self.problems {
             writeln!(f, "{problem}")?;
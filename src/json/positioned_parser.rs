@@ -3,19 +3,32 @@
 use core::ops::{Add, AddAssign};
 
 use jsonschema::paths::{Location, LocationSegment};
-
-/// A position in a file.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde_json::{Map, Value};
+
+/// The maximum depth of nested objects and arrays, deeper documents are not parsed to avoid
+/// overflowing the stack. This matches the recursion limit of `serde_json`, so any document it can
+/// parse can be parsed.
+const MAX_DEPTH: usize = 128;
+
+/// A position in a file, ordered by line then column.
+///
+/// Lines and columns are one based, the first character of a file is at `1:1`. There is no zero
+/// sentinel, an unknown position is represented by `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub struct Position {
     /// The line number (not index).
     pub line: usize,
     /// The column number (not index).
     pub column: usize,
+    /// The byte offset from the start of the file.
+    pub offset: usize,
 }
 impl Add<char> for Position {
     type Output = Self;
 
     fn add(mut self, rhs: char) -> Self::Output {
+        self.offset += rhs.len_utf8();
         if rhs == '\n' {
             self.line += 1;
             self.column = 1;
@@ -32,7 +45,11 @@ impl AddAssign<char> for Position {
 }
 impl Default for Position {
     fn default() -> Self {
-        Self { line: 1, column: 1 }
+        Self {
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
     }
 }
 
@@ -42,6 +59,20 @@ pub struct Tag {
     pub value: String,
 }
 
+/// Statistics about a parsed document, such as for reporting progress or warning about deeply
+/// nested documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseStats {
+    /// The number of lines in the document, a trailing newline does not start a new line.
+    pub lines: usize,
+    /// The length of the document in bytes.
+    pub bytes: usize,
+    /// The deepest nesting of objects and arrays, a value has a depth of zero and an object or
+    /// array of values has a depth of one.
+    pub max_depth: usize,
+}
+
 /// A JSON node with it's position in the source file.
 #[derive(Debug)]
 pub enum PositionedJsonNode {
@@ -49,36 +80,108 @@ pub enum PositionedJsonNode {
     Object {
         /// The object's position.
         position: Position,
+        /// The position of the object's closing brace.
+        end: Position,
         /// The object's properties.
-        properties: Vec<(Tag, PositionedJsonNode)>,
+        properties: Vec<(Tag, Self)>,
     },
     /// An array.
     Array {
         /// The array's position.
         position: Position,
+        /// The position of the array's closing bracket.
+        end: Position,
         /// The array's items.
-        items: Vec<PositionedJsonNode>,
+        items: Vec<Self>,
     },
     /// A value.
     Value {
         /// The value's position.
         position: Position,
-        /// The value.
+        /// The position of the value's last character, such as a string's closing quote.
+        end: Position,
+        /// The value, strings are unquoted and unescaped, other values are as written.
         value: String,
+        /// If the value is a string.
+        is_string: bool,
     },
 }
 
 impl PositionedJsonNode {
     /// Try parse a source file into a JSON node while tracking node positions.
+    ///
+    /// Parsing does not check the document is valid JSON and never panics, malformed documents may
+    /// produce a partial node tree. Returns `None` for an empty document, or if objects and arrays
+    /// are nested more than 128 deep.
     pub fn try_parse(src: &str) -> Option<Self> {
+        Self::try_parse_with_stats(src).0
+    }
+
+    /// Try parse a source file into a JSON node, see [`PositionedJsonNode::try_parse`], and
+    /// collect statistics about the document while parsing.
+    ///
+    /// The statistics are collected even if the document could not be parsed, the depth is zero
+    /// if no node was parsed.
+    pub fn try_parse_with_stats(src: &str) -> (Option<Self>, ParseStats) {
         let mut position = Position::default();
         let mut iter = src.chars();
-        Self::parse(&mut position, &mut iter).map(|(node, ..)| node)
+        let mut max_depth = 0;
+        let node =
+            Self::parse(&mut position, &mut iter, 0, &mut max_depth).map(|(node, overeaten)| {
+                if let Some(overeaten) = overeaten {
+                    position += overeaten;
+                }
+                node
+            });
+
+        debug_assert!(
+            node.as_ref().is_none_or(Self::positions_are_one_based),
+            "node positions must be one based"
+        );
+
+        // Scan the rest of the document, such as trailing whitespace, so the position is at the end.
+        for ch in iter {
+            position += ch;
+        }
+
+        let stats = ParseStats {
+            // A newline moves the position to the first column of the next line.
+            lines: if position.column == 1 {
+                position.line - 1
+            } else {
+                position.line
+            },
+            bytes: position.offset,
+            max_depth: if node.is_some() { max_depth } else { 0 },
+        };
+
+        (node, stats)
     }
 
+    /// Check the positions of the node and it's children are one based.
+    fn positions_are_one_based(&self) -> bool {
+        let (position, end) = (self.position(), self.end_position());
+        if position.line == 0 || position.column == 0 || end.line == 0 || end.column == 0 {
+            return false;
+        }
+
+        match &self {
+            Self::Object { properties, .. } => properties.iter().all(|(tag, property)| {
+                tag.position.line > 0
+                    && tag.position.column > 0
+                    && property.positions_are_one_based()
+            }),
+            Self::Array { items, .. } => items.iter().all(Self::positions_are_one_based),
+            Self::Value { .. } => true,
+        }
+    }
+
+    /// Parse the next node, recording the deepest nesting of objects and arrays in `max_depth`.
     fn parse<T: Iterator<Item = char>>(
         current_position: &mut Position,
         src: &mut T,
+        depth: usize,
+        max_depth: &mut usize,
     ) -> Option<(Self, Option<char>)> {
         while let Some(ch) = src.next() {
             if ch.is_whitespace() {
@@ -87,18 +190,14 @@ impl PositionedJsonNode {
             }
 
             if ch == '{' {
-                *current_position += ch;
-                let object = Self::parse_object(current_position, src)?;
+                let object = Self::parse_object(current_position, src, depth + 1, max_depth)?;
                 return Some((object, None));
             } else if ch == '[' {
-                *current_position += ch;
-                let array = Self::parse_array(current_position, src)?;
+                let array = Self::parse_array(current_position, src, depth + 1, max_depth)?;
                 return Some((array, None));
             } else if ch == '\"' {
-                let position = *current_position;
-                *current_position += ch;
-                let value = Self::parse_string(current_position, src)?;
-                return Some((Self::Value { position, value }, None));
+                let string = Self::parse_string_value(current_position, src)?;
+                return Some((string, None));
             } else {
                 let value = Self::parse_value(current_position, src, ch)?;
                 return Some(value);
@@ -108,18 +207,28 @@ impl PositionedJsonNode {
         None
     }
 
+    /// Parse an object, the current position is the opening brace which has been consumed.
     fn parse_object<T: Iterator<Item = char>>(
         current_position: &mut Position,
         src: &mut T,
+        depth: usize,
+        max_depth: &mut usize,
     ) -> Option<Self> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        *max_depth = (*max_depth).max(depth);
+
         let position = *current_position;
+        *current_position += '{';
+        let mut end = position;
         let mut properties = vec![];
 
         while let Some(ch) = src.next() {
             if ch == '\"' {
                 let tag_position = *current_position;
                 *current_position += ch;
-                let tag = Self::parse_string(current_position, src)?;
+                let (tag, _) = Self::parse_string(current_position, src)?;
                 let tag = Tag {
                     position: tag_position,
                     value: tag,
@@ -134,15 +243,20 @@ impl PositionedJsonNode {
                     }
                 }
 
-                let (property, overeaten) = Self::parse(current_position, src)?;
+                let (property, overeaten) = Self::parse(current_position, src, depth, max_depth)?;
                 properties.push((tag, property));
 
-                if let Some(overeaten) = overeaten
-                    && overeaten == '}'
-                {
-                    break;
+                if let Some(overeaten) = overeaten {
+                    let overeaten_position = *current_position;
+                    *current_position += overeaten;
+
+                    if overeaten == '}' {
+                        end = overeaten_position;
+                        break;
+                    }
                 }
             } else if ch == '}' {
+                end = *current_position;
                 *current_position += ch;
                 break;
             } else {
@@ -152,15 +266,26 @@ impl PositionedJsonNode {
 
         Some(Self::Object {
             position,
+            end,
             properties,
         })
     }
 
+    /// Parse an array, the current position is the opening bracket which has been consumed.
     fn parse_array<T: Iterator<Item = char>>(
         current_position: &mut Position,
         src: &mut T,
+        depth: usize,
+        max_depth: &mut usize,
     ) -> Option<Self> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        *max_depth = (*max_depth).max(depth);
+
         let position = *current_position;
+        *current_position += '[';
+        let mut end = position;
         let mut items = vec![];
 
         while let Some(ch) = src.next() {
@@ -168,54 +293,85 @@ impl PositionedJsonNode {
                 *current_position += ch;
                 continue;
             } else if ch == ']' {
+                end = *current_position;
                 *current_position += ch;
                 break;
             } else if ch == '\"' {
-                let position = *current_position;
-                *current_position += ch;
-                let value = Self::parse_string(current_position, src)?;
-                items.push(Self::Value { position, value });
+                let string = Self::parse_string_value(current_position, src)?;
+                items.push(string);
             } else if ch == '{' {
-                *current_position += ch;
-                let object = Self::parse_object(current_position, src)?;
+                let object = Self::parse_object(current_position, src, depth + 1, max_depth)?;
                 items.push(object);
             } else if ch == '[' {
-                *current_position += ch;
-                let array = Self::parse_array(current_position, src)?;
+                let array = Self::parse_array(current_position, src, depth + 1, max_depth)?;
                 items.push(array);
             } else {
                 let (value, overeaten) = Self::parse_value(current_position, src, ch)?;
                 items.push(value);
 
-                if let Some(overeaten) = overeaten
-                    && overeaten == ']'
-                {
-                    break;
+                if let Some(overeaten) = overeaten {
+                    let overeaten_position = *current_position;
+                    *current_position += overeaten;
+
+                    if overeaten == ']' {
+                        end = overeaten_position;
+                        break;
+                    }
                 }
             }
         }
 
-        Some(Self::Array { position, items })
+        Some(Self::Array {
+            position,
+            end,
+            items,
+        })
+    }
+
+    /// Parse a string value, the current position is the opening quote which has been consumed.
+    fn parse_string_value<T: Iterator<Item = char>>(
+        current_position: &mut Position,
+        src: &mut T,
+    ) -> Option<Self> {
+        let position = *current_position;
+        *current_position += '\"';
+        let (value, end) = Self::parse_string(current_position, src)?;
+
+        Some(Self::Value {
+            position,
+            end,
+            value,
+            is_string: true,
+        })
     }
 
+    /// Parse the rest of a string, returning the unescaped string and the position of the closing
+    /// quote.
     fn parse_string<T: Iterator<Item = char>>(
         current_position: &mut Position,
         src: &mut T,
-    ) -> Option<String> {
+    ) -> Option<(String, Position)> {
         let mut value = String::new();
+        let mut end = *current_position;
 
-        let mut is_escaped = false;
         while let Some(ch) = src.next() {
+            end = *current_position;
             *current_position += ch;
 
-            if is_escaped {
-                value.push(ch);
-                is_escaped = false;
-                continue;
-            }
-
             if ch == '\\' {
-                is_escaped = true;
+                let escaped = src.next()?;
+                *current_position += escaped;
+
+                let unescaped = match escaped {
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => Self::parse_unicode_escape(current_position, src)?,
+                    _ => escaped,
+                };
+                value.push(unescaped);
             } else if ch == '\"' {
                 break;
             } else {
@@ -223,9 +379,51 @@ impl PositionedJsonNode {
             }
         }
 
-        Some(value)
+        Some((value, end))
     }
 
+    /// Parse the code point of a `\u` escape, combining UTF-16 surrogate pairs. Unpaired
+    /// surrogates are replaced with the replacement character.
+    fn parse_unicode_escape<T: Iterator<Item = char>>(
+        current_position: &mut Position,
+        src: &mut T,
+    ) -> Option<char> {
+        let parse_hex = |current_position: &mut Position, src: &mut T| {
+            let mut code = 0;
+            for _ in 0..4 {
+                let ch = src.next()?;
+                *current_position += ch;
+                code = code * 16 + ch.to_digit(16)?;
+            }
+            Some(code)
+        };
+
+        let code = parse_hex(current_position, src)?;
+        if !(0xD800..0xDC00).contains(&code) {
+            return Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+
+        // A high surrogate must be followed by an escaped low surrogate.
+        for expected in ['\\', 'u'] {
+            let ch = src.next()?;
+            *current_position += ch;
+            if ch != expected {
+                return Some(char::REPLACEMENT_CHARACTER);
+            }
+        }
+        let low = parse_hex(current_position, src)?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Some(char::REPLACEMENT_CHARACTER);
+        }
+
+        let code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+        Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Parse a value that is not a string, object, or array, such as a number.
+    ///
+    /// A closing brace or bracket ending the value is returned without being consumed from the
+    /// current position.
     fn parse_value<T: Iterator<Item = char>>(
         current_position: &mut Position,
         src: &mut T,
@@ -233,44 +431,125 @@ impl PositionedJsonNode {
     ) -> Option<(Self, Option<char>)> {
         let position = *current_position;
         *current_position += first_char;
+        let mut end = position;
         let mut value = first_char.to_string();
 
         let mut overeaten = None;
         while let Some(ch) = src.next() {
+            if ch == '}' || ch == ']' {
+                overeaten = Some(ch);
+                break;
+            }
+
+            let ch_position = *current_position;
             *current_position += ch;
 
             if ch.is_whitespace() || ch == ',' {
                 break;
-            } else if ch == '}' || ch == ']' {
-                overeaten = Some(ch);
-                break;
-            } else {
-                value.push(ch);
+            }
+
+            end = ch_position;
+            value.push(ch);
+        }
+
+        Some((
+            Self::Value {
+                position,
+                end,
+                value,
+                is_string: false,
+            },
+            overeaten,
+        ))
+    }
+
+    /// Convert the node into the JSON value it represents.
+    ///
+    /// Parsing does not check the document is valid JSON, so this should only be used for
+    /// documents that are known to be valid. Returns `None` if a value is invalid.
+    ///
+    /// This is not faster than parsing the document again with `serde_json`, which is also
+    /// required to check the document is valid.
+    pub fn to_value(&self) -> Option<Value> {
+        match &self {
+            Self::Object { properties, .. } => properties
+                .iter()
+                .map(|(tag, property)| Some((tag.value.clone(), property.to_value()?)))
+                .collect::<Option<Map<_, _>>>()
+                .map(Value::Object),
+            Self::Array { items, .. } => items
+                .iter()
+                .map(Self::to_value)
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array),
+            Self::Value {
+                value, is_string, ..
+            } => {
+                if *is_string {
+                    return Some(Value::String(value.clone()));
+                }
+
+                match value.as_str() {
+                    "null" => Some(Value::Null),
+                    "true" => Some(Value::Bool(true)),
+                    "false" => Some(Value::Bool(false)),
+                    number => number.parse().ok().map(Value::Number),
+                }
             }
         }
+    }
 
-        Some((Self::Value { position, value }, overeaten))
+    /// Convert the node into the JSON value it represents, keeping the order of the object
+    /// properties in the document, such as to write back a config without reordering the user's
+    /// properties.
+    ///
+    /// Requires the `preserve_order` feature, which enables `serde_json`'s `preserve_order`
+    /// feature so objects keep their insertion order. See [`to_value`](Self::to_value).
+    #[cfg(feature = "preserve_order")]
+    pub fn to_ordered_value(&self) -> Option<Value> {
+        self.to_value()
     }
 
     /// Try evaluate a pointer to the node it is pointing at.
     pub fn evaluate(&self, pointer: &Location) -> Option<&Self> {
-        let segments = pointer.into_iter();
+        self.evaluate_segments(pointer.into_iter())
+    }
+
+    /// Try evaluate a pointer to the object or array containing the node it is pointing at, such
+    /// as to select the whole containing value of a problem's instance path in an editor using the
+    /// parent's [`position`](Self::position) and [`end_position`](Self::end_position).
+    ///
+    /// The root node has no parent, so this returns `None` for an empty pointer, or if the pointer
+    /// does not resolve.
+    pub fn parent_of(&self, pointer: &Location) -> Option<&Self> {
+        let segments: Vec<_> = pointer.into_iter().collect();
+        let (last, parent_segments) = segments.split_last()?;
+
+        let parent = self.evaluate_segments(parent_segments.iter().copied())?;
+        parent.get_segment(*last)?;
 
+        Some(parent)
+    }
+
+    fn evaluate_segments<'a>(
+        &self,
+        segments: impl Iterator<Item = LocationSegment<'a>>,
+    ) -> Option<&Self> {
         let mut current_node = self;
         for segment in segments {
-            match segment {
-                LocationSegment::Property(tag) => {
-                    current_node = current_node.get(Index::Tag(tag))?
-                }
-                LocationSegment::Index(index) => {
-                    current_node = current_node.get(Index::Index(index))?
-                }
-            }
+            current_node = current_node.get_segment(segment)?;
         }
 
         Some(current_node)
     }
 
+    fn get_segment(&self, segment: LocationSegment<'_>) -> Option<&Self> {
+        match segment {
+            LocationSegment::Property(tag) => self.get(Index::Tag(tag)),
+            LocationSegment::Index(index) => self.get(Index::Index(index)),
+        }
+    }
+
     /// Try index the node.
     pub fn get<'a, 'b>(&'b self, index: Index<'a>) -> Option<&'b Self> {
         match &self {
@@ -298,6 +577,15 @@ impl PositionedJsonNode {
         }
     }
 
+    /// Return the position of the node's last character, such as an object's closing brace.
+    pub fn end_position(&self) -> Position {
+        match &self {
+            Self::Object { end, .. } => *end,
+            Self::Array { end, .. } => *end,
+            Self::Value { end, .. } => *end,
+        }
+    }
+
     /// Return the position of the node.
     pub fn position(&self) -> Position {
         match &self {
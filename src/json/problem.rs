@@ -1,5 +1,5 @@
-use core::{fmt, ops::Range};
-use std::path::PathBuf;
+use core::{cmp::Ordering, fmt, ops::Range};
+use std::path::{Path, PathBuf};
 
 use jsonschema::{ValidationError, error::ValidationErrorKind, paths::Location};
 use serde_json::Value;
@@ -8,9 +8,15 @@ use crate::{
     json::{
         location::LocationExtensions,
         positioned_parser::{Position, PositionedJsonNode},
-        problem_messages::ProblemMessage,
+        problem_messages::{
+            ProblemMessage, count_note, dependency_message, dependency_note, dependency_trigger,
+            tuple_item_note,
+        },
+        problem_style::ProblemStyle,
+        subschema::parse_pointer,
+        suggestion::Suggestion,
     },
-    style::{BOLD, CYAN, RED, RESET, normalize_error},
+    style::{BOLD, RESET, hyperlink, normalize_error},
 };
 
 #[derive(Debug)]
@@ -18,9 +24,47 @@ use crate::{
 pub struct FileLocation {
     pub path: PathBuf,
     pub position: Option<Position>,
+    /// The position of the last character of the value, such as an object's closing brace.
+    pub end: Option<Position>,
+}
+
+/// The file path, instance path, severity, headline, and message of a problem.
+pub(crate) type ProblemIdentity<'a> = (
+    Option<&'a Path>,
+    &'a Location,
+    Severity,
+    String,
+    Option<String>,
+);
+
+/// How severe a validation problem is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The document is invalid.
+    #[default]
+    Error,
+    /// The document is valid, but likely contains a mistake.
+    Warning,
+}
+impl Severity {
+    /// The label for the severity.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
 }
 
 /// A validation problem.
+///
+/// A problem owns all of it's data, so it can outlive the validated instance and be sent between
+/// threads.
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct ValidationProblem {
@@ -29,40 +73,87 @@ pub struct ValidationProblem {
 
     /// The kind of validation problem.
     pub kind: ValidationErrorKind,
+    /// How severe the problem is.
+    pub severity: Severity,
 
     /// Any notes about this validation problem.
     pub notes: Vec<String>,
+    /// A link to documentation about the problem, from the schema's `x-docs-url` annotation.
+    pub docs_url: Option<String>,
+    /// A suggested fix for the problem, for problems that can be fixed mechanically such as an
+    /// unexpected property.
+    pub suggestion: Option<Suggestion>,
 
     /// The JSON pointer to the source of this problem.
     pub instance_path: Location,
+    /// The JSON pointer to the schema keyword that raised this problem.
+    pub schema_path: Location,
     /// The reconstructed JSON source of the problem
     pub source: String,
-    /// The range to underline.
+    /// The byte range of the source to underline.
     pub range: Range<usize>,
+
+    /// A message that replaces the kind's message, such as for a property required by another.
+    message: Option<String>,
 }
 
+// Problems must stay decoupled from the validated instance.
+const _: () = {
+    const fn assert_owned<T: Send + Sync + 'static>() {}
+    assert_owned::<ValidationProblem>();
+};
+
 impl fmt::Display for ValidationProblem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.write_headline(f)?;
-        self.write_file(f)?;
-        self.write_spacer(f)?;
-        self.write_source(f)?;
-        self.write_message(f)?;
-
-        if !self.notes.is_empty() {
-            self.write_spacer(f)?;
+        self.write(f, &ProblemStyle::default())
+    }
+}
 
-            for note in &self.notes {
-                self.write_symbol(" = ", f)?;
-                writeln!(f, "{BOLD}note:{RESET} {note}")?;
-            }
-        }
+/// A validation problem rendered with a style.
+struct StyledProblem<'a> {
+    problem: &'a ValidationProblem,
+    style: &'a ProblemStyle,
+}
+impl fmt::Display for StyledProblem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.problem.write(f, self.style)
+    }
+}
 
-        Ok(())
+/// Problems are compared by their file path, instance path, and severity, then by their rendered
+/// headline and message as the [`ValidationErrorKind`] is not comparable.
+impl Ord for ValidationProblem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.identity().cmp(&other.identity())
+    }
+}
+impl PartialOrd for ValidationProblem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for ValidationProblem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
+impl Eq for ValidationProblem {}
 
 impl ValidationProblem {
+    /// What identifies the problem when comparing problems, the schema path is ignored as it
+    /// differs between schemas.
+    pub(crate) fn identity(&self) -> ProblemIdentity<'_> {
+        (
+            self.location
+                .as_ref()
+                .map(|location| location.path.as_path()),
+            &self.instance_path,
+            self.severity,
+            self.kind.headline(),
+            self.message(),
+        )
+    }
+
     /// Create a new validation problem from a validation error.
     pub fn new(
         problem: ValidationError<'_>,
@@ -77,12 +168,16 @@ impl ValidationProblem {
             schema_path,
         } = problem;
 
+        let trigger = dependency_trigger(&kind, &instance, &schema_path, schema);
+        let message = trigger
+            .as_deref()
+            .and_then(|trigger| dependency_message(&kind, trigger));
+
         let notes = {
             let mut notes = Vec::new();
 
-            if let Some(parent) = schema_path.parent()
-                && let Some(node) = schema.pointer(parent.join("description").as_str())
-                && let Some(contents) = node.as_str()
+            if let Some(contents) =
+                annotation(schema, &schema_path, "description").and_then(Value::as_str)
             {
                 let mut lines = contents.split('\n');
 
@@ -95,28 +190,42 @@ impl ValidationProblem {
                 }
             };
 
+            if message.is_none()
+                && let Some(trigger) = &trigger
+            {
+                notes.push(dependency_note(trigger));
+            }
+
+            if let Some(note) = tuple_item_note(&kind, &instance_path, &schema_path) {
+                notes.push(note);
+            }
+
+            if let Some(note) = count_note(&kind, &instance) {
+                notes.push(note);
+            }
+
             notes
         };
 
-        let (source, range) = {
-            let source = instance_path
-                .reconstruct(&instance)
-                .lines()
-                .nth(0)
-                .map_or(String::new(), |v| v.to_string());
+        let docs_url = annotation(schema, &schema_path, "x-docs-url")
+            .and_then(Value::as_str)
+            .map(str::to_string);
 
-            let range = source.find(": ").map(|v| v + 2).unwrap_or(0)..source.len();
+        let (source, range) = Self::source_and_range(&instance_path, &instance);
 
-            (source, range)
-        };
+        let suggestion = document.and_then(|document| {
+            Suggestion::for_problem(&kind, &instance_path, schema, &schema_path, document)
+        });
 
         let location = if let Some(document) = document
             && let Some(path) = file_path
         {
-            let position = document
-                .evaluate(&instance_path)
-                .map(|node| node.position());
-            Some(FileLocation { path, position })
+            let node = document.evaluate(&instance_path);
+            Some(FileLocation {
+                path,
+                position: node.map(PositionedJsonNode::position),
+                end: node.map(PositionedJsonNode::end_position),
+            })
         } else {
             None
         };
@@ -124,14 +233,289 @@ impl ValidationProblem {
         Self {
             location,
             kind,
+            severity: Severity::Error,
             notes,
+            docs_url,
+            suggestion,
+            instance_path,
+            schema_path,
+            source,
+            range,
+            message,
+        }
+    }
+
+    /// Create a new validation problem from only a validation error, without notes from the schema
+    /// or a file location.
+    pub fn from_error_only(problem: ValidationError<'_>) -> Self {
+        let ValidationError {
+            instance,
+            kind,
+            instance_path,
+            schema_path,
+        } = problem;
+
+        let (source, range) = Self::source_and_range(&instance_path, &instance);
+
+        Self {
+            location: None,
+            kind,
+            severity: Severity::Error,
+            notes: Vec::new(),
+            docs_url: None,
+            suggestion: None,
+            instance_path,
+            schema_path,
+            source,
+            range,
+            message: None,
+        }
+    }
+
+    /// Create a new validation problem at the root of a document from a message, for problems
+    /// found outside of validation such as a document that could not be read.
+    pub fn from_message<S: ToString>(message: S, file_path: Option<PathBuf>) -> Self {
+        Self {
+            location: file_path.map(|path| FileLocation {
+                path,
+                position: None,
+                end: None,
+            }),
+            kind: ValidationErrorKind::Custom {
+                message: message.to_string(),
+            },
+            severity: Severity::Error,
+            notes: Vec::new(),
+            docs_url: None,
+            suggestion: None,
+            instance_path: Location::new(),
+            schema_path: Location::new(),
+            source: String::new(),
+            range: 0..0,
+            message: None,
+        }
+    }
+
+    /// Create a new validation problem from a message for the value at the JSON pointer in a
+    /// document, for problems found outside of validation such as rules the schema can't express.
+    ///
+    /// If the pointer does not resolve in the document, the problem is at the root.
+    pub fn from_message_at<S: ToString>(
+        message: S,
+        pointer: &str,
+        instance: &Value,
+        document: Option<&PositionedJsonNode>,
+        file_path: Option<PathBuf>,
+    ) -> Self {
+        let mut instance_path = Location::new();
+        let mut value = instance;
+        for segment in parse_pointer(pointer) {
+            let child = match value {
+                Value::Object(object) => object.get(&segment).map(|child| (child, None)),
+                Value::Array(array) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| Some((array.get(index)?, Some(index)))),
+                _ => None,
+            };
+            let Some((child, index)) = child else {
+                instance_path = Location::new();
+                value = instance;
+                break;
+            };
+
+            instance_path = match index {
+                Some(index) => instance_path.join(index),
+                None => instance_path.join(segment.as_str()),
+            };
+            value = child;
+        }
+
+        let (source, range) = Self::source_and_range(&instance_path, value);
+
+        let location = file_path.map(|path| {
+            let node = document.and_then(|document| document.evaluate(&instance_path));
+            FileLocation {
+                path,
+                position: node.map(PositionedJsonNode::position),
+                end: node.map(PositionedJsonNode::end_position),
+            }
+        });
+
+        Self {
+            location,
+            kind: ValidationErrorKind::Custom {
+                message: message.to_string(),
+            },
+            severity: Severity::Error,
+            notes: Vec::new(),
+            docs_url: None,
+            suggestion: None,
             instance_path,
+            schema_path: Location::new(),
             source,
             range,
+            message: None,
         }
     }
 
-    fn indent(&self) -> usize {
+    /// Create a new validation problem from a JSON syntax error, underlining the offending token.
+    pub fn from_syntax_error(
+        error: &serde_json::Error,
+        raw_document: &str,
+        file_path: Option<PathBuf>,
+    ) -> Self {
+        let line = raw_document
+            .lines()
+            .nth(error.line().saturating_sub(1))
+            .unwrap_or_default();
+
+        // `serde_json` reports the column in bytes.
+        let mut offset = error.column().saturating_sub(1).min(line.len());
+        while !line.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let column = line[..offset].chars().count() + 1;
+
+        let indent = line.len() - line.trim_start().len();
+        let start = offset - indent.min(offset);
+        let end = start + line[offset..].chars().next().map_or(1, char::len_utf8);
+
+        let suffix = format!(" at line {} column {}", error.line(), error.column());
+        let message = error.to_string().replacen("EOF", "end of file", 1);
+        let message = message.strip_suffix(&suffix).unwrap_or(&message);
+
+        let line_offset: usize = raw_document
+            .split_inclusive('\n')
+            .take(error.line().saturating_sub(1))
+            .map(str::len)
+            .sum();
+
+        // `serde_json` reports line zero when the error has no position.
+        let position = (error.line() > 0).then_some(Position {
+            line: error.line(),
+            column,
+            offset: line_offset + offset,
+        });
+        let location = file_path.map(|path| FileLocation {
+            path,
+            position,
+            end: position,
+        });
+
+        Self {
+            location,
+            kind: ValidationErrorKind::Custom {
+                message: message.to_string(),
+            },
+            severity: Severity::Error,
+            notes: Vec::new(),
+            docs_url: None,
+            suggestion: None,
+            instance_path: Location::new(),
+            schema_path: Location::new(),
+            source: line.trim().to_string(),
+            range: start..end,
+            message: None,
+        }
+    }
+
+    /// Reconstruct the source of the problem and the range to underline.
+    fn source_and_range(instance_path: &Location, instance: &Value) -> (String, Range<usize>) {
+        let source = instance_path
+            .reconstruct(instance)
+            .lines()
+            .nth(0)
+            .map_or(String::new(), |v| v.to_string());
+
+        let range = source.find(": ").map(|v| v + 2).unwrap_or(0)..source.len();
+
+        (source, range)
+    }
+
+    /// The stable kebab-case identifier for the problem's rule, such as `required` or
+    /// `additional-properties`.
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+
+    /// The message explaining the problem, shown beside the underline.
+    pub fn message(&self) -> Option<String> {
+        self.message.clone().or_else(|| self.kind.message())
+    }
+
+    /// The key for the canonical ordering of problems, by severity with errors first, then file
+    /// position, then instance path.
+    ///
+    /// Problems without a position are ordered before problems with a position.
+    pub fn sort_key(&self) -> (Severity, Option<Position>, &Location) {
+        let position = self
+            .location
+            .as_ref()
+            .and_then(|location| location.position);
+        (self.severity, position, &self.instance_path)
+    }
+
+    /// Display the problem using the style.
+    pub fn display_with<'a>(&'a self, style: &'a ProblemStyle) -> impl fmt::Display + 'a {
+        StyledProblem {
+            problem: self,
+            style,
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, style: &ProblemStyle) -> fmt::Result {
+        self.write_headline(f, style)?;
+        self.write_file(f, style)?;
+        self.write_spacer(f, style)?;
+        self.write_source(f, style)?;
+        self.write_message(f, style)?;
+
+        if !self.notes.is_empty() || self.docs_url.is_some() {
+            self.write_spacer(f, style)?;
+
+            for note in &self.notes {
+                self.write_note(note, f, style)?;
+            }
+
+            if let Some(url) = &self.docs_url {
+                self.write_symbol(&format!(" {} ", style.chars.note), f, style)?;
+                writeln!(f, "{BOLD}{}see:{RESET} {}", style.note, hyperlink(url, url))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a note, wrapping it to the style's maximum note width.
+    fn write_note(
+        &self,
+        note: &str,
+        f: &mut fmt::Formatter<'_>,
+        style: &ProblemStyle,
+    ) -> fmt::Result {
+        const LABEL: &str = "note: ";
+
+        let symbol = format!(" {} ", style.chars.note);
+        let text_column = self.indent() + symbol.chars().count() + LABEL.len();
+        let lines = match style.max_note_width {
+            Some(width) => wrap(note, width.saturating_sub(text_column)),
+            None => vec![note.to_string()],
+        };
+
+        for (index, line) in lines.iter().enumerate() {
+            if index == 0 {
+                self.write_symbol(&symbol, f, style)?;
+                writeln!(f, "{BOLD}{}note:{RESET} {line}", style.note)?;
+            } else {
+                writeln!(f, "{}{line}", " ".repeat(text_column))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn indent(&self) -> usize {
         if let Some(location) = &self.location
             && let Some(position) = location.position
         {
@@ -141,19 +525,22 @@ impl ValidationProblem {
         }
     }
 
-    fn write_headline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn write_headline(&self, f: &mut fmt::Formatter<'_>, style: &ProblemStyle) -> fmt::Result {
         let headline = self.kind.headline();
         let node = self.instance_path.pointing_at();
+        let severity = self.severity;
+        let code = self.code();
 
         writeln!(
             f,
-            "{RED}{BOLD}error{RESET}{BOLD}: `{node}` {headline}{RESET}"
+            "{}{BOLD}{severity}[{code}]{RESET}{BOLD}: `{node}` {headline}{RESET}",
+            style.severity(severity)
         )
     }
 
-    fn write_file(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn write_file(&self, f: &mut fmt::Formatter<'_>, style: &ProblemStyle) -> fmt::Result {
         if let Some(location) = self.location.as_ref() {
-            self.write_symbol("--> ", f)?;
+            self.write_symbol(&format!("{} ", style.chars.arrow), f, style)?;
             write!(f, "{}", location.path.to_string_lossy())?;
             if let Some(position) = location.position {
                 write!(f, ":{}:{}", position.line, position.column)?;
@@ -164,39 +551,109 @@ impl ValidationProblem {
         }
     }
 
-    fn write_spacer(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.write_symbol(" | ", f)?;
+    fn write_spacer(&self, f: &mut fmt::Formatter<'_>, style: &ProblemStyle) -> fmt::Result {
+        self.write_symbol(&format!(" {} ", style.chars.gutter), f, style)?;
         writeln!(f)
     }
 
-    fn write_symbol(&self, symbol: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn write_symbol(
+        &self,
+        symbol: &str,
+        f: &mut fmt::Formatter<'_>,
+        style: &ProblemStyle,
+    ) -> fmt::Result {
         let indent = " ".repeat(self.indent());
-        write!(f, "{indent}{BOLD}{CYAN}{symbol}{RESET}")
+        write!(f, "{indent}{BOLD}{}{symbol}{RESET}", style.gutter)
     }
 
-    fn write_source(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(location) = &self.location
-            && let Some(position) = location.position
-        {
-            let line = position.line;
-            write!(f, "{BOLD}{CYAN}{line}{RESET}")?;
+    fn write_source(&self, f: &mut fmt::Formatter<'_>, style: &ProblemStyle) -> fmt::Result {
+        let gutter = style.gutter;
+
+        // Right align the line number within the indent so the gutter lines up with the symbols.
+        let line = self
+            .location
+            .as_ref()
+            .and_then(|location| location.position)
+            .map_or_else(String::new, |position| position.line.to_string());
+        let width = self.indent();
+
+        let symbol = style.chars.gutter;
+        let (source, _) = self.visible_source(style);
+
+        writeln!(f, "{BOLD}{gutter}{line:>width$} {symbol} {RESET}{source}")
+    }
+
+    /// The source to show and the range to underline within it, cut to the style's maximum width.
+    ///
+    /// The returned range is in characters, so it can be used to align the underline.
+    fn visible_source(&self, style: &ProblemStyle) -> (String, Range<usize>) {
+        let chars: Vec<char> = self.source.chars().collect();
+        let range = self.char_range();
+
+        let Some(width) = style.max_source_width else {
+            return (self.source.clone(), range);
+        };
+        if chars.len() <= width || width == 0 {
+            return (self.source.clone(), range);
+        }
+
+        // Center the window on the range, keeping the start of the range and some context visible.
+        let start = if range.len() > width {
+            range.start.saturating_sub(width / 4)
+        } else {
+            let center = range.start + range.len() / 2;
+            center.saturating_sub(width / 2).min(range.start)
+        }
+        .min(chars.len() - width);
+        let end = start + width;
+
+        let mut source = String::new();
+        if start > 0 {
+            source.push('…');
+        }
+        source.extend(&chars[start..end]);
+        if end < chars.len() {
+            source.push('…');
         }
 
-        writeln!(f, "{BOLD}{CYAN} | {RESET}{}", self.source)
+        let offset = usize::from(start > 0);
+        let range = (range.start.clamp(start, end) - start + offset)
+            ..(range.end.clamp(start, end) - start + offset);
+
+        (source, range)
+    }
+
+    /// The range to underline as character indices into the source.
+    pub(crate) fn char_range(&self) -> Range<usize> {
+        let index = |byte: usize| {
+            self.source
+                .char_indices()
+                .take_while(|(index, _)| *index < byte)
+                .count()
+        };
+
+        index(self.range.start)..index(self.range.end)
     }
 
-    fn write_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.write_symbol(" | ", f)?;
+    fn write_message(&self, f: &mut fmt::Formatter<'_>, style: &ProblemStyle) -> fmt::Result {
+        let caret = match self.severity {
+            Severity::Error => style.caret,
+            Severity::Warning => style.warning,
+        };
 
+        self.write_symbol(&format!(" {} ", style.chars.gutter), f, style)?;
+
+        // Always show at least one caret, even for an empty range.
+        let (_, range) = self.visible_source(style);
         write!(
             f,
-            "{}{RED}{BOLD}{}{RESET}",
-            " ".repeat(self.range.start),
-            "^".repeat(self.range.len()),
+            "{}{caret}{BOLD}{}{RESET}",
+            " ".repeat(range.start),
+            style.chars.caret.repeat(range.len().max(1)),
         )?;
 
-        if let Some(message) = self.kind.message() {
-            writeln!(f, " {RED}{BOLD}{message}{RESET}")?;
+        if let Some(message) = self.message() {
+            writeln!(f, " {caret}{BOLD}{message}{RESET}")?;
         } else {
             writeln!(f)?
         }
@@ -204,3 +661,66 @@ impl ValidationProblem {
         Ok(())
     }
 }
+
+/// Find an annotation on the schema that produced an error.
+///
+/// The schema path is followed through any local `$ref`s. The annotation is taken from the
+/// outermost schema that has it, starting at the schema that references the producing schema and
+/// falling back through the `$ref` targets.
+pub(super) fn annotation<'a>(
+    schema: &'a Value,
+    schema_path: &Location,
+    keyword: &str,
+) -> Option<&'a Value> {
+    let segments = parse_pointer(schema_path.as_str());
+    let (_, parent) = segments.split_last()?;
+
+    // The schemas joined by `$ref`s that the parent resolves to.
+    let mut candidates = vec![schema];
+    for segment in parent {
+        let node = *candidates.last()?;
+
+        if segment == "$ref" {
+            let reference = node.get("$ref")?.as_str()?.strip_prefix('#')?;
+            candidates.push(schema.pointer(reference)?);
+        } else {
+            let child = match node {
+                Value::Object(object) => object.get(segment)?,
+                Value::Array(array) => array.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+            candidates = vec![child];
+        }
+    }
+
+    candidates
+        .into_iter()
+        .find_map(|candidate| candidate.get(keyword))
+}
+
+/// Wrap the text at spaces so each line is at most the width, words longer than the width are
+/// kept whole.
+///
+/// The text is not wrapped if the width is too narrow to fit a few words.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    const MIN_WIDTH: usize = 20;
+
+    if width < MIN_WIDTH || text.chars().count() <= width {
+        return vec![text.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ') {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(core::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+
+    lines
+}
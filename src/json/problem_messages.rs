@@ -1,7 +1,43 @@
 use jsonschema::{
     JsonType,
     error::{TypeKind, ValidationErrorKind},
+    paths::{Location, LocationSegment},
 };
+use serde_json::Value;
+
+use crate::{
+    json::{location::LocationExtensions, subschema::parse_pointer},
+    style::normalize_error,
+};
+
+/// The keywords that apply their subschemas to the children of the instance.
+const CHILD_KEYWORDS: [&str; 10] = [
+    "properties",
+    "patternProperties",
+    "additionalProperties",
+    "unevaluatedProperties",
+    "propertyNames",
+    "items",
+    "prefixItems",
+    "additionalItems",
+    "unevaluatedItems",
+    "contains",
+];
+
+/// The keywords that apply a constraint when a property is present.
+const DEPENDENCY_KEYWORDS: [&str; 3] = ["dependencies", "dependentRequired", "dependentSchemas"];
+
+/// The keywords whose next schema path segment is a name, such as a property name, rather than a
+/// keyword.
+const NAMED_KEYWORDS: [&str; 7] = [
+    "properties",
+    "patternProperties",
+    "$defs",
+    "definitions",
+    "dependencies",
+    "dependentRequired",
+    "dependentSchemas",
+];
 
 pub trait ProblemMessage {
     /// The specific problem's message, should be in the form `this [imperative] [detail]`.
@@ -17,6 +53,13 @@ pub trait ProblemMessage {
     /// * `is missing a required property`
     /// * `is too large`
     fn headline(&self) -> String;
+
+    /// The stable kebab-case identifier for the problem's rule.
+    ///
+    /// Examples:
+    /// * `required`
+    /// * `additional-properties`
+    fn code(&self) -> &'static str;
 }
 
 impl ProblemMessage for ValidationErrorKind {
@@ -29,15 +72,28 @@ impl ProblemMessage for ValidationErrorKind {
                 "this should not have the properties [{}]",
                 unexpected.join(", ")
             )),
-            Self::Constant { expected_value } => Some(format!("this should be {expected_value}")),
+            Self::Constant { expected_value } => Some(format!(
+                "this should be {}",
+                format_json_scalar(expected_value)
+            )),
             Self::ContentEncoding { content_encoding } => {
                 Some(format!("this should be encoded as {content_encoding}"))
             }
             Self::ContentMediaType { content_media_type } => Some(format!(
                 "this should be the {content_media_type} media type"
             )),
-            Self::Custom { message } => Some(message.to_string()),
-            Self::Enum { options } => Some(format!("this should be one of {options}")),
+            Self::Custom { message } => Some(normalize_error(message)),
+            Self::Enum { options } => {
+                let options = match options.as_array() {
+                    Some(options) => options
+                        .iter()
+                        .map(format_json_scalar)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    None => format_json_scalar(options),
+                };
+                Some(format!("this should be one of [{options}]"))
+            }
             Self::ExclusiveMaximum { limit } => Some(format!("this should be less than {limit}")),
             Self::ExclusiveMinimum { limit } => {
                 Some(format!("this should be greater then {limit}"))
@@ -82,7 +138,12 @@ impl ProblemMessage for ValidationErrorKind {
                 Some(format!("this exceeded the backtrack limit: {error}"))
             }
 
-            _ => None,
+            Self::AnyOf
+            | Self::Contains
+            | Self::FalseSchema
+            | Self::OneOfMultipleValid
+            | Self::OneOfNotValid
+            | Self::UniqueItems => None,
         }
     }
 
@@ -125,6 +186,205 @@ impl ProblemMessage for ValidationErrorKind {
             | Self::UnevaluatedProperties { .. } => "could not be validated".to_string(),
         }
     }
+
+    fn code(&self) -> &'static str {
+        match &self {
+            Self::AdditionalItems { .. } => "additional-items",
+            Self::AdditionalProperties { .. } => "additional-properties",
+            Self::AnyOf => "any-of",
+            Self::BacktrackLimitExceeded { .. } => "backtrack-limit-exceeded",
+            Self::Constant { .. } => "const",
+            Self::Contains => "contains",
+            Self::ContentEncoding { .. } => "content-encoding",
+            Self::ContentMediaType { .. } => "content-media-type",
+            Self::Custom { .. } => "custom",
+            Self::Enum { .. } => "enum",
+            Self::ExclusiveMaximum { .. } => "exclusive-maximum",
+            Self::ExclusiveMinimum { .. } => "exclusive-minimum",
+            Self::FalseSchema => "false-schema",
+            Self::Format { .. } => "format",
+            Self::FromUtf8 { .. } => "from-utf8",
+            Self::MaxItems { .. } => "max-items",
+            Self::Maximum { .. } => "maximum",
+            Self::MaxLength { .. } => "max-length",
+            Self::MaxProperties { .. } => "max-properties",
+            Self::MinItems { .. } => "min-items",
+            Self::Minimum { .. } => "minimum",
+            Self::MinLength { .. } => "min-length",
+            Self::MinProperties { .. } => "min-properties",
+            Self::MultipleOf { .. } => "multiple-of",
+            Self::Not { .. } => "not",
+            Self::OneOfMultipleValid => "one-of-multiple-valid",
+            Self::OneOfNotValid => "one-of-not-valid",
+            Self::Pattern { .. } => "pattern",
+            Self::PropertyNames { .. } => "property-names",
+            Self::Referencing(_) => "referencing",
+            Self::Required { .. } => "required",
+            Self::Type { .. } => "type",
+            Self::UnevaluatedItems { .. } => "unevaluated-items",
+            Self::UnevaluatedProperties { .. } => "unevaluated-properties",
+            Self::UniqueItems => "unique-items",
+        }
+    }
+}
+
+/// The property that triggered a problem raised by a dependency keyword.
+///
+/// The triggering property is taken from the schema path when it is present, otherwise the
+/// dependency in the schema that requires the missing property and is set in the instance is used.
+pub(crate) fn dependency_trigger(
+    kind: &ValidationErrorKind,
+    instance: &Value,
+    schema_path: &Location,
+    schema: &Value,
+) -> Option<String> {
+    let segments = parse_pointer(schema_path.as_str());
+
+    // Only segments in a keyword position are keywords, a property named `dependencies` is not.
+    let mut keyword = None;
+    let mut is_name = false;
+    for (index, segment) in segments.iter().enumerate() {
+        if is_name {
+            is_name = false;
+            continue;
+        }
+        if DEPENDENCY_KEYWORDS.contains(&segment.as_str()) {
+            keyword = Some(index);
+        }
+        is_name = NAMED_KEYWORDS.contains(&segment.as_str());
+    }
+    let keyword = keyword?;
+
+    if let Some(trigger) = segments.get(keyword + 1) {
+        return Some(trigger.clone());
+    }
+
+    let ValidationErrorKind::Required { property } = kind else {
+        return None;
+    };
+
+    // The keyword is the last segment, so the schema path points at the dependencies.
+    let dependencies = schema.pointer(schema_path.as_str())?.as_object()?;
+
+    dependencies
+        .iter()
+        .find(|(trigger, required)| {
+            instance.get(trigger.as_str()).is_some()
+                && required
+                    .as_array()
+                    .is_some_and(|required| required.contains(property))
+        })
+        .map(|(trigger, _)| trigger.clone())
+}
+
+/// The message for a problem raised by a dependency keyword, such as
+/// ``because `useTls` is set, `certPath` is required``.
+///
+/// Only missing properties have a tailored message, other problems are explained by
+/// [`dependency_note`].
+pub(crate) fn dependency_message(kind: &ValidationErrorKind, trigger: &str) -> Option<String> {
+    let ValidationErrorKind::Required { property } = kind else {
+        return None;
+    };
+
+    Some(format!(
+        "because `{trigger}` is set, `{}` is required",
+        property.as_str()?
+    ))
+}
+
+/// Explain which property triggered a problem raised by a dependency keyword.
+pub(crate) fn dependency_note(trigger: &str) -> String {
+    format!("this applies because `{trigger}` is set")
+}
+
+/// State how many properties, items, or characters the instance has when it has too few or too
+/// many, such as `this has 1 property, it needs at least 2`.
+pub(crate) fn count_note(kind: &ValidationErrorKind, instance: &Value) -> Option<String> {
+    let (count, unit, limit) = match kind {
+        ValidationErrorKind::MinProperties { limit }
+        | ValidationErrorKind::MaxProperties { limit } => {
+            let count = instance.as_object()?.len();
+            (
+                count,
+                if count == 1 { "property" } else { "properties" },
+                limit,
+            )
+        }
+        ValidationErrorKind::MinItems { limit } | ValidationErrorKind::MaxItems { limit } => {
+            let count = instance.as_array()?.len();
+            (count, if count == 1 { "item" } else { "items" }, limit)
+        }
+        ValidationErrorKind::MinLength { limit } | ValidationErrorKind::MaxLength { limit } => {
+            let count = instance.as_str()?.chars().count();
+            (
+                count,
+                if count == 1 {
+                    "character"
+                } else {
+                    "characters"
+                },
+                limit,
+            )
+        }
+        _ => return None,
+    };
+
+    let bound = match kind {
+        ValidationErrorKind::MinProperties { .. }
+        | ValidationErrorKind::MinItems { .. }
+        | ValidationErrorKind::MinLength { .. } => "it needs at least",
+        _ => "it can have at most",
+    };
+
+    Some(format!("this has {count} {unit}, {bound} {limit}"))
+}
+
+/// Restate the problem with the item's position when it is raised for an item of a tuple.
+///
+/// An item is in a tuple when the schema path applies `prefixItems`, or `items` as an array in
+/// older drafts, to the item the instance path points at.
+pub(crate) fn tuple_item_note(
+    kind: &ValidationErrorKind,
+    instance_path: &Location,
+    schema_path: &Location,
+) -> Option<String> {
+    let Some(LocationSegment::Index(index)) = instance_path.last() else {
+        return None;
+    };
+
+    let segments = parse_pointer(schema_path.as_str());
+    let keyword = segments
+        .iter()
+        .rposition(|segment| CHILD_KEYWORDS.contains(&segment.as_str()))?;
+    if !matches!(segments[keyword].as_str(), "prefixItems" | "items")
+        || segments.get(keyword + 1) != Some(&index.to_string())
+    {
+        return None;
+    }
+
+    let message = kind.message()?;
+    let detail = message.strip_prefix("this ")?;
+    Some(format!("the {} item {detail}", ordinal(index + 1)))
+}
+
+/// Format a number as an ordinal, such as `1st` or `12th`.
+fn ordinal(number: usize) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{number}{suffix}")
+}
+
+/// Format a JSON value by it's JSON representation, strings are quoted and numbers, booleans, and
+/// null are bare.
+fn format_json_scalar(value: &Value) -> String {
+    value.to_string()
 }
 
 fn display_type_kind(kind: &TypeKind) -> String {
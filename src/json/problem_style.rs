@@ -0,0 +1,82 @@
+use crate::{
+    json::Severity,
+    style::{CYAN, colours, terminal_width},
+};
+
+/// The colours used to render a validation problem.
+///
+/// Each colour is combined with bold when rendered, the default uses the colours of the active
+/// [`Palette`](crate::style::Palette).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProblemStyle {
+    /// The colour of the severity label in the headline.
+    pub headline: &'static str,
+    /// The colour of the severity label, underline, and message for warnings.
+    pub warning: &'static str,
+    /// The colour of the underline and its message.
+    pub caret: &'static str,
+    /// The colour of the gutter, line numbers, and symbols.
+    pub gutter: &'static str,
+    /// The colour of the `note:` label.
+    pub note: &'static str,
+    /// The characters used for the markers.
+    pub chars: DiagnosticChars,
+    /// The maximum number of characters of the source to show, longer sources are cut around the
+    /// underline with `…`.
+    pub max_source_width: Option<usize>,
+    /// The maximum width of a note's lines including the gutter, longer notes are wrapped at
+    /// spaces with the continued lines aligned under the note's text.
+    ///
+    /// Defaults to the terminal width. Notes are not wrapped if this is `None`, such as when the
+    /// width is unknown because the output is piped.
+    pub max_note_width: Option<usize>,
+}
+impl Default for ProblemStyle {
+    fn default() -> Self {
+        let colours = colours();
+        Self {
+            headline: colours.error,
+            warning: colours.warning,
+            caret: colours.error,
+            gutter: CYAN,
+            note: "",
+            chars: DiagnosticChars::default(),
+            max_source_width: Some(100),
+            max_note_width: terminal_width(),
+        }
+    }
+}
+impl ProblemStyle {
+    /// The colour of the severity label for the severity.
+    pub fn severity(&self, severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => self.headline,
+            Severity::Warning => self.warning,
+        }
+    }
+}
+
+/// The characters used to render the markers of a validation problem.
+///
+/// The default matches `rustc`'s diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiagnosticChars {
+    /// The marker before the file location.
+    pub arrow: &'static str,
+    /// The gutter between the line numbers and the source.
+    pub gutter: &'static str,
+    /// The marker before each note.
+    pub note: &'static str,
+    /// The marker repeated to underline the problem.
+    pub caret: &'static str,
+}
+impl Default for DiagnosticChars {
+    fn default() -> Self {
+        Self {
+            arrow: "-->",
+            gutter: "|",
+            note: "=",
+            caret: "^",
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use core::slice;
+use std::collections::BTreeSet;
+
+use serde_json::{Value, json};
+
+use crate::json::{
+    LintReport, ValidationErrors, location::LocationExtensions, problem_messages::ProblemMessage,
+};
+
+/// The SARIF version the reports conform to.
+const SARIF_VERSION: &str = "2.1.0";
+/// The SARIF schema the reports conform to.
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+impl ValidationErrors {
+    /// Render the problems as a SARIF log, reported as the tool, for code scanning integrations.
+    pub fn to_sarif(&self, tool_name: &str) -> Value {
+        sarif(tool_name, slice::from_ref(self))
+    }
+}
+
+impl LintReport {
+    /// Render the problems for every document as a single SARIF log, reported as the tool, for
+    /// code scanning integrations.
+    pub fn to_sarif(&self, tool_name: &str) -> Value {
+        sarif(tool_name, &self.files)
+    }
+}
+
+fn sarif(tool_name: &str, files: &[ValidationErrors]) -> Value {
+    let mut rules = BTreeSet::new();
+    let mut results = Vec::new();
+
+    for file in files {
+        for problem in &file.problems {
+            rules.insert(problem.code());
+
+            let mut text = format!(
+                "`{}` {}",
+                problem.instance_path.pointing_at(),
+                problem.kind.headline()
+            );
+            if let Some(message) = problem.message() {
+                text.push_str(&format!(", {message}"));
+            }
+            for note in &problem.notes {
+                text.push_str(&format!("\nnote: {note}"));
+            }
+
+            let path = problem
+                .location
+                .as_ref()
+                .map(|location| &location.path)
+                .or(file.file_path.as_ref());
+            let mut locations = Vec::new();
+            if let Some(path) = path {
+                let mut physical_location = json!({
+                    "artifactLocation": { "uri": path.to_string_lossy() },
+                });
+                if let Some(position) = problem
+                    .location
+                    .as_ref()
+                    .and_then(|location| location.position)
+                {
+                    physical_location["region"] = json!({
+                        "startLine": position.line,
+                        "startColumn": position.column,
+                    });
+                }
+                locations.push(json!({ "physicalLocation": physical_location }));
+            }
+
+            results.push(json!({
+                "ruleId": problem.code(),
+                "level": problem.severity.label(),
+                "message": { "text": text },
+                "locations": locations,
+                "properties": { "instancePath": problem.instance_path.to_json_pointer() },
+            }));
+        }
+    }
+
+    let rules: Vec<_> = rules
+        .into_iter()
+        .map(|code| json!({ "id": code }))
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": { "driver": { "name": tool_name, "rules": rules } },
+            "results": results,
+        }],
+    })
+}
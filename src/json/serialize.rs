@@ -0,0 +1,94 @@
+use serde::{Serialize, Serializer, ser::SerializeStruct};
+
+use crate::json::{
+    Edit, LintReport, Position, Severity, Suggestion, ValidationErrors, ValidationProblem,
+    location::LocationExtensions, problem::FileLocation, problem_messages::ProblemMessage,
+};
+
+/// Problems are serialized with their instance and schema paths as JSON pointers, and their kind
+/// as it's stable code, headline, and message.
+impl Serialize for ValidationProblem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ValidationProblem", 12)?;
+        state.serialize_field("severity", &self.severity)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("headline", &self.kind.headline())?;
+        state.serialize_field("message", &self.message())?;
+        state.serialize_field("notes", &self.notes)?;
+        state.serialize_field("docs_url", &self.docs_url)?;
+        state.serialize_field("suggestion", &self.suggestion)?;
+        state.serialize_field("location", &self.location)?;
+        state.serialize_field("instance_path", &self.instance_path.to_json_pointer())?;
+        state.serialize_field("schema_path", &self.schema_path.to_json_pointer())?;
+        state.serialize_field("source", &self.source)?;
+        state.serialize_field("range", &self.range)?;
+        state.end()
+    }
+}
+
+impl Serialize for ValidationErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ValidationErrors", 4)?;
+        state.serialize_field(
+            "file_path",
+            &self.file_path.as_ref().map(|path| path.to_string_lossy()),
+        )?;
+        state.serialize_field("label", &self.label)?;
+        state.serialize_field("problems", &self.problems)?;
+        state.serialize_field("suppressed", &self.suppressed)?;
+        state.end()
+    }
+}
+
+impl Serialize for LintReport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("LintReport", 1)?;
+        state.serialize_field("files", &self.files)?;
+        state.end()
+    }
+}
+
+impl Serialize for FileLocation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("FileLocation", 3)?;
+        state.serialize_field("path", &self.path.to_string_lossy())?;
+        state.serialize_field("position", &self.position)?;
+        state.serialize_field("end", &self.end)?;
+        state.end()
+    }
+}
+
+impl Serialize for Position {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Position", 3)?;
+        state.serialize_field("line", &self.line)?;
+        state.serialize_field("column", &self.column)?;
+        state.serialize_field("offset", &self.offset)?;
+        state.end()
+    }
+}
+
+impl Serialize for Suggestion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Suggestion", 2)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("edits", &self.edits)?;
+        state.end()
+    }
+}
+
+impl Serialize for Edit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Edit", 2)?;
+        state.serialize_field("range", &self.range)?;
+        state.serialize_field("replacement", &self.replacement)?;
+        state.end()
+    }
+}
+
+/// Severities are serialized as their label, `error` or `warning`.
+impl Serialize for Severity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.label())
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+
+use jsonschema::{Draft, ValidationError, ValidationOptions, paths::Location};
+use serde_json::{Value, json};
+
+use crate::json::{
+    PositionedJsonNode, SchemaError, ValidationProblem, build_validator, escape_segment,
+};
+
+/// The URI the root schema is registered under when validating a subtree.
+const ROOT_SCHEMA_URI: &str = "urn:ts-rust-helper:root-schema";
+/// The maximum number of chained references to follow when finding a subschema.
+const MAX_REFERENCE_DEPTH: usize = 32;
+
+/// Validate the subtree of a JSON instance at the JSON pointer against the corresponding subschema.
+///
+/// The subschema is found by following `properties`, `additionalProperties`, `prefixItems`, and
+/// `items` from the root schema, resolving local `$ref`s along the way. Reported instance paths
+/// are absolute so they can be resolved against the full document.
+///
+/// Only the subschema is applied, so constraints that depend on the subtree's ancestors or
+/// siblings such as `required`, `dependencies`, `dependentRequired`, `dependentSchemas`,
+/// `if`/`then`/`else`, `allOf`/`anyOf`/`oneOf`, `patternProperties`, and `unevaluatedProperties`
+/// are not checked. If the pointer does not resolve in the instance, or no subschema can be found,
+/// the subtree is considered valid.
+///
+/// Returns the problems with the subtree, empty if it is valid, or an error if the subschema can't
+/// be used to create a validator.
+pub fn validate_at(
+    schema: &Value,
+    instance: &Value,
+    pointer: &str,
+    validation_options: ValidationOptions,
+    document: Option<&PositionedJsonNode>,
+    file_path: Option<PathBuf>,
+) -> Result<Vec<ValidationProblem>, SchemaError> {
+    let Some(subtree) = instance.pointer(pointer) else {
+        return Ok(Vec::new());
+    };
+
+    let prefix = parse_pointer(pointer);
+    let Some(schema_pointer) = subschema_pointer(schema, &prefix) else {
+        return Ok(Vec::new());
+    };
+
+    let root = Draft::default()
+        .detect(schema)
+        .unwrap_or_default()
+        .create_resource(schema.clone());
+    let validation_options = validation_options.with_resource(ROOT_SCHEMA_URI, root);
+    let subschema = json!({ "$ref": format!("{ROOT_SCHEMA_URI}#{schema_pointer}") });
+
+    let validator = build_validator(&subschema, &validation_options)?;
+    let schema_prefix = parse_pointer(&schema_pointer);
+
+    let problems = validator
+        .iter_errors(subtree)
+        .map(|error| {
+            let error = ValidationError {
+                instance_path: join(&prefix, &error.instance_path, 0),
+                // Skip the wrapping `$ref`.
+                schema_path: join(&schema_prefix, &error.schema_path, 1),
+                ..error
+            };
+
+            ValidationProblem::new(error, schema, document, file_path.clone())
+        })
+        .collect();
+
+    Ok(problems)
+}
+
+/// Find the pointer to the subschema that applies to the instance path.
+fn subschema_pointer(schema: &Value, instance_path: &[String]) -> Option<String> {
+    let mut pointer = String::new();
+    let mut node = schema;
+
+    for segment in instance_path {
+        (pointer, node) = resolve_references(schema, pointer, node)?;
+
+        let (path, next) = child_schema(node, segment)?;
+        pointer.push_str(&path);
+        node = next;
+    }
+
+    let (pointer, _) = resolve_references(schema, pointer, node)?;
+    Some(pointer)
+}
+
+/// Follow any local references from the node, bounded to guard against reference cycles.
+pub(crate) fn resolve_references<'a>(
+    schema: &'a Value,
+    mut pointer: String,
+    mut node: &'a Value,
+) -> Option<(String, &'a Value)> {
+    let mut depth = 0;
+    while let Some(reference) = node.get("$ref").and_then(Value::as_str)
+        && let Some(reference) = reference.strip_prefix('#')
+    {
+        depth += 1;
+        if depth > MAX_REFERENCE_DEPTH {
+            return None;
+        }
+
+        pointer = reference.to_string();
+        node = schema.pointer(&pointer)?;
+    }
+
+    Some((pointer, node))
+}
+
+/// Find the schema that applies to the child of an instance, returning the path to it relative to
+/// the parent schema.
+pub(crate) fn child_schema<'a>(node: &'a Value, segment: &str) -> Option<(String, &'a Value)> {
+    if let Some(property) = node.get("properties").and_then(|v| v.get(segment)) {
+        return Some((format!("/properties/{}", escape_segment(segment)), property));
+    }
+
+    if let Ok(index) = segment.parse::<usize>() {
+        if let Some(items) = node.get("prefixItems")
+            && let Some(item) = items.get(index)
+        {
+            return Some((format!("/prefixItems/{index}"), item));
+        }
+
+        if let Some(items) = node.get("items") {
+            if items.is_object() {
+                return Some(("/items".to_string(), items));
+            } else if let Some(item) = items.get(index) {
+                return Some((format!("/items/{index}"), item));
+            }
+        }
+    }
+
+    node.get("additionalProperties")
+        .filter(|additional| additional.is_object())
+        .map(|additional| ("/additionalProperties".to_string(), additional))
+}
+
+/// Parse a JSON pointer into it's unescaped segments.
+pub(crate) fn parse_pointer(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Join the prefix segments with the location, skipping the first `skip` segments of the
+/// location.
+pub(crate) fn join(prefix: &[String], location: &Location, skip: usize) -> Location {
+    let segments = parse_pointer(location.as_str());
+
+    prefix
+        .iter()
+        .chain(segments.iter().skip(skip))
+        .fold(Location::new(), |location, segment| {
+            match segment.parse::<usize>() {
+                Ok(index) => location.join(index),
+                Err(_) => location.join(segment.as_str()),
+            }
+        })
+}
@@ -0,0 +1,187 @@
+use core::ops::Range;
+
+use jsonschema::{error::ValidationErrorKind, paths::Location};
+use serde_json::Value;
+
+use crate::json::{
+    positioned_parser::{Position, PositionedJsonNode, Tag},
+    problem::annotation,
+};
+
+/// A suggested fix for a validation problem, as edits to the source document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Suggestion {
+    /// What the fix does, such as ``remove the property `name` ``.
+    pub message: String,
+    /// The edits to the source, ordered by their position and never overlapping.
+    pub edits: Vec<Edit>,
+}
+
+/// An edit to a source document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Edit {
+    /// The byte range of the source to replace, empty for an insertion.
+    pub range: Range<usize>,
+    /// The text to replace the range with, empty for a deletion.
+    pub replacement: String,
+}
+
+impl Suggestion {
+    /// Suggest a fix for the problem, if it can be fixed mechanically.
+    ///
+    /// Unexpected properties are removed, and missing required properties are inserted if the
+    /// schema has a default for them.
+    pub(crate) fn for_problem(
+        kind: &ValidationErrorKind,
+        instance_path: &Location,
+        schema: &Value,
+        schema_path: &Location,
+        document: &PositionedJsonNode,
+    ) -> Option<Self> {
+        let PositionedJsonNode::Object {
+            position,
+            properties,
+            ..
+        } = document.evaluate(instance_path)?
+        else {
+            return None;
+        };
+
+        match kind {
+            ValidationErrorKind::AdditionalProperties { unexpected } => {
+                let mut ranges: Vec<Range<usize>> = unexpected
+                    .iter()
+                    .filter_map(|name| {
+                        let index = properties.iter().position(|(tag, _)| &tag.value == name)?;
+                        Some(property_range(properties, index))
+                    })
+                    .collect();
+                if ranges.is_empty() {
+                    return None;
+                }
+
+                // Removing neighbouring properties produces overlapping ranges.
+                ranges.sort_by_key(|range| range.start);
+                let mut edits: Vec<Edit> = Vec::new();
+                for range in ranges {
+                    match edits.last_mut() {
+                        Some(edit) if range.start <= edit.range.end => {
+                            edit.range.end = edit.range.end.max(range.end);
+                        }
+                        _ => edits.push(Edit {
+                            range,
+                            replacement: String::new(),
+                        }),
+                    }
+                }
+
+                let names: Vec<_> = unexpected.iter().map(|name| format!("`{name}`")).collect();
+                let message = if names.len() == 1 {
+                    format!("remove the property {}", names.join(", "))
+                } else {
+                    format!("remove the properties {}", names.join(", "))
+                };
+
+                Some(Self { message, edits })
+            }
+
+            ValidationErrorKind::Required { property } => {
+                let name = property.as_str()?;
+                let default = property_default(schema, schema_path, name)?;
+
+                let property = format!(
+                    "{}: {}",
+                    serde_json::to_string(name).ok()?,
+                    serde_json::to_string(default).ok()?
+                );
+                let edit = match properties.last() {
+                    Some((_, last)) => Edit {
+                        range: after(last.end_position())..after(last.end_position()),
+                        replacement: format!(", {property}"),
+                    },
+                    None => Edit {
+                        range: after(*position)..after(*position),
+                        replacement: property,
+                    },
+                };
+
+                Some(Self {
+                    message: format!("add the property `{name}` with the default `{default}`"),
+                    edits: vec![edit],
+                })
+            }
+
+            _ => None,
+        }
+    }
+}
+
+/// Apply the suggestions to the source, skipping any suggestion with an edit that overlaps or
+/// touches an edit of an earlier suggestion, or that is out of the source's bounds.
+///
+/// Returns the edited source and the indices of the suggestions that were applied. Skipped
+/// suggestions may apply once the edited source is validated again.
+pub fn apply_suggestions(source: &str, suggestions: &[&Suggestion]) -> (String, Vec<usize>) {
+    let mut edits: Vec<&Edit> = Vec::new();
+    let mut applied = Vec::new();
+
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        let conflicts = suggestion.edits.iter().any(|edit| {
+            source.get(edit.range.clone()).is_none()
+                || edits.iter().any(|other| {
+                    edit.range.start <= other.range.end && other.range.start <= edit.range.end
+                })
+        });
+        if conflicts {
+            continue;
+        }
+
+        edits.extend(&suggestion.edits);
+        applied.push(index);
+    }
+
+    edits.sort_by_key(|edit| edit.range.start);
+    let mut output = source.to_string();
+    for edit in edits.iter().rev() {
+        output.replace_range(edit.range.clone(), &edit.replacement);
+    }
+
+    (output, applied)
+}
+
+/// The range to remove a property from an object, including the comma that separates it from its
+/// neighbours.
+fn property_range(properties: &[(Tag, PositionedJsonNode)], index: usize) -> Range<usize> {
+    let (tag, value) = &properties[index];
+
+    match (properties.get(index + 1), index.checked_sub(1)) {
+        // Remove up to the next property's key.
+        (Some((next, _)), _) => tag.position.offset..next.position.offset,
+        // Remove from the end of the previous property's value.
+        (None, Some(previous)) => {
+            after(properties[previous].1.end_position())..after(value.end_position())
+        }
+        (None, None) => tag.position.offset..after(value.end_position()),
+    }
+}
+
+/// The default for the property from the schema whose `required` keyword is at the schema path.
+fn property_default<'a>(
+    schema: &'a Value,
+    schema_path: &Location,
+    name: &str,
+) -> Option<&'a Value> {
+    let property = annotation(schema, schema_path, "properties")?.get(name)?;
+
+    property.get("default").or_else(|| {
+        let reference = property.get("$ref")?.as_str()?.strip_prefix('#')?;
+        schema.pointer(reference)?.get("default")
+    })
+}
+
+/// The byte offset after the single byte character at the position, such as a closing brace.
+fn after(position: Position) -> usize {
+    position.offset + 1
+}
@@ -0,0 +1,123 @@
+use std::{borrow::Cow, path::PathBuf};
+
+use jsonschema::{ValidationError, error::ValidationErrorKind, paths::Location};
+use serde_json::Value;
+
+use crate::json::{
+    PositionedJsonNode, Severity, ValidationProblem,
+    subschema::{child_schema, join, parse_pointer, resolve_references},
+};
+
+/// Find the properties of a JSON instance that the schema allows but does not describe.
+///
+/// An object is checked when it's schema lists `properties` and allows additional properties
+/// without describing them, that is `additionalProperties` is absent or `true` and there are no
+/// `patternProperties`. Known properties and items are checked recursively, resolving local `$ref`s
+/// along the way. Each object with unknown properties produces a warning.
+pub fn unknown_properties(
+    schema: &Value,
+    instance: &Value,
+    document: Option<&PositionedJsonNode>,
+    file_path: Option<PathBuf>,
+) -> Vec<ValidationProblem> {
+    let mut errors = Vec::new();
+    walk(
+        schema,
+        String::new(),
+        schema,
+        instance,
+        Location::new(),
+        &mut errors,
+    );
+
+    errors
+        .into_iter()
+        .map(|error| {
+            let mut problem = ValidationProblem::new(error, schema, document, file_path.clone());
+            problem.severity = Severity::Warning;
+            problem
+        })
+        .collect()
+}
+
+fn walk<'i>(
+    root: &Value,
+    pointer: String,
+    node: &Value,
+    instance: &'i Value,
+    instance_path: Location,
+    errors: &mut Vec<ValidationError<'i>>,
+) {
+    let Some((pointer, node)) = resolve_references(root, pointer, node) else {
+        return;
+    };
+
+    let children: Vec<(String, &Value)> = match instance {
+        Value::Object(object) => {
+            if let Some(properties) = node.get("properties").and_then(Value::as_object)
+                && allows_unknown_properties(node)
+            {
+                let unexpected: Vec<String> = object
+                    .keys()
+                    .filter(|key| !properties.contains_key(key.as_str()))
+                    .cloned()
+                    .collect();
+
+                if !unexpected.is_empty() {
+                    let schema_path = join(
+                        &parse_pointer(&format!("{pointer}/additionalProperties")),
+                        &Location::new(),
+                        0,
+                    );
+
+                    errors.push(ValidationError {
+                        instance: Cow::Borrowed(instance),
+                        kind: ValidationErrorKind::AdditionalProperties { unexpected },
+                        instance_path: instance_path.clone(),
+                        schema_path,
+                    });
+                }
+            }
+
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), value))
+                .collect()
+        }
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), item))
+            .collect(),
+        _ => return,
+    };
+
+    for (segment, child) in children {
+        let Some((path, child_node)) = child_schema(node, &segment) else {
+            continue;
+        };
+
+        let child_path = match segment.parse::<usize>() {
+            Ok(index) if instance.is_array() => instance_path.join(index),
+            _ => instance_path.join(segment.as_str()),
+        };
+
+        walk(
+            root,
+            format!("{pointer}{path}"),
+            child_node,
+            child,
+            child_path,
+            errors,
+        );
+    }
+}
+
+/// Check if the schema allows properties it does not describe.
+fn allows_unknown_properties(node: &Value) -> bool {
+    let additional = node
+        .get("additionalProperties")
+        .is_none_or(|additional| additional == &Value::Bool(true));
+
+    additional && node.get("patternProperties").is_none()
+}
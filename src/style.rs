@@ -1,5 +1,7 @@
 //! Feature toggle for output style
 
+use core::sync::atomic::{AtomicU8, Ordering};
+
 #[cfg(feature = "styled")]
 pub use styled::*;
 #[cfg(not(feature = "styled"))]
@@ -7,14 +9,37 @@ pub use unstyled::*;
 
 /// Make the first letter lowercase and remove any trailing punctuation.
 pub fn normalize_error(message: &str) -> String {
+    normalize(message, false)
+}
+
+/// Make the first letter lowercase unless the first word is an acronym, and remove any trailing
+/// punctuation.
+///
+/// The first word is an acronym if it is at least two characters and has no lowercase letters,
+/// such as `HTTP` or `URL`.
+pub fn normalize_error_preserve_acronyms(message: &str) -> String {
+    normalize(message, true)
+}
+
+fn normalize(message: &str, preserve_acronyms: bool) -> String {
     const ILLEGAL_LAST_CHARS: [char; 3] = ['.', '?', '!'];
 
-    let mut chars = message.trim().chars();
+    let message = message.trim();
+    let mut chars = message.chars();
+
+    let first_word = message.split_whitespace().next().unwrap_or_default();
+    let is_acronym = first_word.chars().count() >= 2
+        && first_word.chars().any(char::is_uppercase)
+        && !first_word.chars().any(char::is_lowercase);
 
     // Make first letter lowercase.
-    let first_char = chars
-        .next()
-        .map_or_else(String::new, |v| v.to_lowercase().to_string());
+    let first_char = chars.next().map_or_else(String::new, |v| {
+        if preserve_acronyms && is_acronym {
+            v.to_string()
+        } else {
+            v.to_lowercase().to_string()
+        }
+    });
 
     // Remove trailing punctuation.
     let last_char = if let Some(real_last_char) = chars.next_back()
@@ -30,6 +55,218 @@ pub fn normalize_error(message: &str) -> String {
     format!("{first_char}{middle}{last_char}")
 }
 
+/// Render the text as a hyperlink to the URL.
+///
+/// The link is written as an OSC 8 escape sequence, terminals that do not support them show only
+/// the text. When the `styled` feature is disabled the text is returned as is.
+pub fn hyperlink(url: &str, text: &str) -> String {
+    #[cfg(feature = "styled")]
+    {
+        format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+    }
+    #[cfg(not(feature = "styled"))]
+    {
+        let _ = url;
+        text.to_string()
+    }
+}
+
+/// Remove the ANSI escape sequences from the text, such as the style constants and hyperlinks.
+///
+/// CSI sequences like `\x1b[1m` and OSC sequences like the OSC 8 hyperlinks from [`hyperlink`] are
+/// removed, keeping a hyperlink's text.
+pub fn strip_ansi(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if char != '\x1b' {
+            output.push(char);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI, ends at the first final byte.
+            Some('[') => {
+                for char in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&char) {
+                        break;
+                    }
+                }
+            }
+            // OSC, ends at BEL or ST.
+            Some(']') => {
+                while let Some(char) = chars.next() {
+                    if char == '\x07' {
+                        break;
+                    }
+                    if char == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Other escape sequences are a single character.
+            _ => {}
+        }
+    }
+
+    output
+}
+
+/// The width of the terminal in columns.
+///
+/// With the `terminal_size` feature the width of the terminal attached to stdout is used, otherwise
+/// the `COLUMNS` environment variable set by most shells is used.
+pub fn terminal_width() -> Option<usize> {
+    #[cfg(feature = "terminal_size")]
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return Some(usize::from(width));
+    }
+
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|columns| *columns > 0)
+}
+
+/// The environment variable read to select the [`Palette`] if none has been set, such as
+/// `APP_PALETTE=deuteranopia`.
+pub const PALETTE_ENV_VAR: &str = "APP_PALETTE";
+
+/// The colours used to distinguish outcomes in diagnostics and command output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Palette {
+    /// Red errors, yellow warnings, and green success.
+    #[default]
+    Default,
+    /// Orange errors, yellow warnings, and blue success, avoiding red and green for colour-blind
+    /// users.
+    Deuteranopia,
+}
+
+/// The colours of a [`Palette`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Colours {
+    /// The colour of errors and failures.
+    pub error: &'static str,
+    /// The colour of warnings.
+    pub warning: &'static str,
+    /// The colour of success.
+    pub success: &'static str,
+}
+
+impl Palette {
+    /// The colours of the palette.
+    pub fn colours(self) -> Colours {
+        match self {
+            Self::Default => Colours {
+                error: RED,
+                warning: YELLOW,
+                success: GREEN,
+            },
+            Self::Deuteranopia => Colours {
+                error: ORANGE,
+                warning: YELLOW,
+                success: BLUE,
+            },
+        }
+    }
+
+    /// The palette named by the [`PALETTE_ENV_VAR`] environment variable, `default` or
+    /// `deuteranopia`, if it is set to a known palette.
+    pub fn from_env() -> Option<Self> {
+        let name = std::env::var(PALETTE_ENV_VAR).ok()?;
+        match name.trim().to_ascii_lowercase().as_str() {
+            "default" => Some(Self::Default),
+            "deuteranopia" => Some(Self::Deuteranopia),
+            _ => None,
+        }
+    }
+}
+
+static PALETTE: AtomicU8 = AtomicU8::new(0);
+
+/// Set the active palette, overriding the [`PALETTE_ENV_VAR`] environment variable.
+pub fn set_palette(palette: Palette) {
+    let value = match palette {
+        Palette::Default => 1,
+        Palette::Deuteranopia => 2,
+    };
+    PALETTE.store(value, Ordering::Relaxed);
+}
+
+/// The active palette, set by [`set_palette`], or read from the [`PALETTE_ENV_VAR`] environment
+/// variable the first time it is needed.
+pub fn palette() -> Palette {
+    match PALETTE.load(Ordering::Relaxed) {
+        1 => Palette::Default,
+        2 => Palette::Deuteranopia,
+        _ => {
+            let palette = Palette::from_env().unwrap_or_default();
+            set_palette(palette);
+            palette
+        }
+    }
+}
+
+/// The colours of the active palette, see [`palette`].
+pub fn colours() -> Colours {
+    palette().colours()
+}
+
+/// Prepare the terminal for styled output, this should be called once at startup.
+///
+/// On Windows this enables virtual terminal processing so older consoles render the escape
+/// sequences instead of printing them literally. On other platforms, or when the `styled` feature
+/// is disabled, this is a no-op.
+pub fn init() {
+    #[cfg(all(feature = "styled", windows))]
+    {
+        static INIT: std::sync::Once = std::sync::Once::new();
+        INIT.call_once(windows::enable_virtual_terminal_processing);
+    }
+}
+
+#[cfg(all(feature = "styled", windows))]
+mod windows {
+    use core::ffi::c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = 0xFFFF_FFF5;
+    const STD_ERROR_HANDLE: u32 = 0xFFFF_FFF4;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetStdHandle(std_handle: u32) -> *mut c_void;
+        fn GetConsoleMode(console_handle: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console_handle: *mut c_void, mode: u32) -> i32;
+    }
+
+    /// Enable virtual terminal processing on stdout and stderr, handles that are not consoles are
+    /// skipped.
+    pub fn enable_virtual_terminal_processing() {
+        for std_handle in [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE] {
+            // SAFETY: the handle is only used if it is a valid console handle.
+            unsafe {
+                let handle = GetStdHandle(std_handle);
+                if handle.is_null() || handle.addr() == usize::MAX {
+                    continue;
+                }
+
+                let mut mode = 0;
+                if GetConsoleMode(handle, &mut mode) == 0 {
+                    continue;
+                }
+
+                SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING);
+            }
+        }
+    }
+}
+
 #[cfg(feature = "styled")]
 mod styled {
     /// Reset styling
@@ -147,6 +384,9 @@ mod styled {
     /// Set background of text to dim white
     pub const BG_DIM_WHITE: &str = "\x1b[47m";
 
+    /// Set color of text to orange, from the 256 colour palette
+    pub const ORANGE: &str = "\x1b[38;5;208m";
+
     /// Set color of text to default
     pub const DEFAULT: &str = "\x1b[99m";
     /// Set background of text to default
@@ -285,6 +525,9 @@ mod unstyled {
     /// Set background of text to dim white
     pub const BG_DIM_WHITE: &str = "";
 
+    /// Set color of text to orange, from the 256 colour palette
+    pub const ORANGE: &str = "";
+
     /// Set color of text to default
     pub const DEFAULT: &str = "";
     /// Set background of text to default
@@ -0,0 +1,85 @@
+//! Tests for parsing the CLI.
+
+use std::{env, fs, path::PathBuf, process};
+
+use clap::error::ErrorKind;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use ts_rust_helper::{
+    command::{BuildInfo, Cli, config_command::ConfigSubcommand},
+    config::{ConfigFile, LoadOptions},
+};
+
+#[test]
+fn version_is_the_binary_version() {
+    let build_info = BuildInfo::new("9.8.7");
+    let error = Cli::try_parse_from_with_build_info(&build_info, ["app", "--version"])
+        .expect_err("--version should exit");
+
+    assert_eq!(error.kind(), ErrorKind::DisplayVersion);
+    assert!(error.to_string().contains("9.8.7"));
+    assert!(!error.to_string().contains(env!("CARGO_PKG_VERSION")));
+}
+
+#[test]
+fn version_requires_build_info() {
+    let error =
+        Cli::try_parse_from(["app", "--version"]).expect_err("--version is not an argument");
+
+    assert_eq!(error.kind(), ErrorKind::UnknownArgument);
+}
+
+/// A config with a required integer `level`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct LevelConfig {
+    level: u8,
+}
+
+impl ConfigFile for LevelConfig {
+    fn config_file_path() -> PathBuf {
+        PathBuf::from("level.json")
+    }
+
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "level": { "type": "integer" } },
+            "required": ["level"]
+        })
+    }
+}
+
+#[test]
+fn lint_files_reports_each_file() {
+    let directory = env::temp_dir().join(format!("ts-rust-helper-lint-{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("valid.json"), r#"{ "level": 1 }"#).unwrap();
+    fs::write(directory.join("invalid.json"), r#"{ "level": "high" }"#).unwrap();
+    let missing = directory.join("missing.json");
+
+    let report = ConfigSubcommand::lint_files::<LevelConfig>(
+        &[directory.clone(), missing],
+        &LoadOptions::new(),
+    )
+    .expect("files should be linted");
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(report.files.len(), 3);
+    assert_eq!(report.total(), 2);
+    assert!(report.has_errors());
+}
+
+#[test]
+fn command_name_names_the_subcommand() {
+    let cli = Cli::try_parse_from(["app", "config", "lint"]).unwrap();
+    assert_eq!(cli.command_name(), "config lint");
+
+    let cli = Cli::try_parse_from(["app"]).unwrap();
+    assert!(!cli.command_name().is_empty());
+}
+
+#[test]
+fn lint_accepts_group_items() {
+    let cli = Cli::try_parse_from(["app", "config", "lint", "--group-items"]).unwrap();
+    assert_eq!(cli.command_name(), "config lint");
+}
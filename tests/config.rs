@@ -0,0 +1,205 @@
+//! Tests for loading configs.
+
+use std::{env, fs, path::PathBuf, process, thread};
+
+use jsonschema::{Draft, ValidationOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use ts_rust_helper::{
+    config::{ConfigFile, ConfigStorage, FileStorage, LoadConfigError, LoadOptions},
+    json::Severity,
+};
+
+/// A config whose `level` is a `u8`, but whose schema allows any integer.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct LevelConfig {
+    level: u8,
+}
+
+impl ConfigFile for LevelConfig {
+    fn config_file_path() -> PathBuf {
+        PathBuf::from("level.json")
+    }
+
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "level": { "type": "integer" } },
+            "required": ["level"]
+        })
+    }
+}
+
+#[test]
+fn deserialize_failures_are_errors() {
+    let result =
+        LoadOptions::new().load_from_reader::<LevelConfig, _>(r#"{ "level": 300 }"#.as_bytes());
+
+    assert!(matches!(result, Err(LoadConfigError::Deserialize { .. })));
+}
+
+#[test]
+fn valid_configs_load() {
+    let loaded = LoadOptions::new()
+        .load_from_reader::<LevelConfig, _>(r#"{ "level": 3 }"#.as_bytes())
+        .expect("config should load");
+
+    assert_eq!(loaded.config.level, 3);
+}
+
+#[test]
+fn invalid_extra_schemas_are_errors() {
+    let result = LoadOptions::new().with_schema::<LevelConfig>(json!({ "type": 1 }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn extra_schemas_are_validated_against() {
+    let options = LoadOptions::new()
+        .with_schema::<LevelConfig>(json!({
+            "properties": { "level": { "maximum": 2 } }
+        }))
+        .expect("schema should be valid");
+
+    let result = options.load_from_reader::<LevelConfig, _>(r#"{ "level": 3 }"#.as_bytes());
+
+    assert!(matches!(
+        result,
+        Err(LoadConfigError::ValidationError { .. })
+    ));
+}
+
+/// A config validated as draft 7 whose schema declares draft 2020-12.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Draft7Config {
+    level: u8,
+}
+
+impl ConfigFile for Draft7Config {
+    fn config_file_path() -> PathBuf {
+        PathBuf::from("draft7.json")
+    }
+
+    fn schema() -> serde_json::Value {
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": { "level": { "type": "integer" } }
+        })
+    }
+
+    fn validation_options() -> ValidationOptions {
+        jsonschema::options().with_draft(Draft::Draft7)
+    }
+}
+
+#[test]
+fn draft_mismatches_are_warnings() {
+    let loaded = LoadOptions::new()
+        .load_from_reader::<Draft7Config, _>(r#"{ "level": 3 }"#.as_bytes())
+        .expect("config should load");
+
+    assert_eq!(loaded.problems.problems.len(), 1);
+    let problem = &loaded.problems.problems[0];
+    assert_eq!(problem.severity, Severity::Warning);
+    assert!(
+        problem
+            .message()
+            .is_some_and(|message| message.contains("draft-07"))
+    );
+
+    let result = LoadOptions::new()
+        .strict()
+        .load_from_reader::<Draft7Config, _>(r#"{ "level": 3 }"#.as_bytes());
+    assert!(matches!(
+        result,
+        Err(LoadConfigError::ValidationError { .. })
+    ));
+}
+
+#[test]
+fn concurrent_writes_leave_no_temporary_files() {
+    let directory = env::temp_dir().join(format!("ts-rust-helper-write-{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("config.json");
+
+    thread::scope(|scope| {
+        for index in 0..8 {
+            let path = &path;
+            scope.spawn(move || {
+                FileStorage
+                    .write(path, format!("{index}").as_bytes())
+                    .expect("write should succeed");
+            });
+        }
+    });
+
+    let entries: Vec<_> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(entries, ["config.json"]);
+    assert!(contents.parse::<u8>().is_ok_and(|index| index < 8));
+}
+
+#[test]
+fn failed_writes_leave_no_temporary_files() {
+    let directory = env::temp_dir().join(format!("ts-rust-helper-failed-{}", process::id()));
+    fs::create_dir_all(directory.join("config.json")).unwrap();
+
+    let result = FileStorage.write(&directory.join("config.json"), b"{}");
+
+    let entries: Vec<_> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert!(result.is_err());
+    assert_eq!(entries, ["config.json"]);
+}
+
+/// A config with a list of servers.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ServersConfig {
+    servers: Vec<u16>,
+}
+
+impl ConfigFile for ServersConfig {
+    fn config_file_path() -> PathBuf {
+        PathBuf::from("servers.json")
+    }
+
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "servers": { "type": "array", "items": { "type": "integer" } } }
+        })
+    }
+}
+
+#[test]
+fn group_by_index_groups_loaded_problems() {
+    let source = r#"{ "servers": ["a", 2, "c"] }"#;
+
+    let errors = LoadOptions::new()
+        .load_from_reader::<ServersConfig, _>(source.as_bytes())
+        .expect_err("config should be invalid")
+        .into_validation_errors();
+    assert_eq!(errors.problems.len(), 2);
+
+    let errors = LoadOptions::new()
+        .group_by_index()
+        .load_from_reader::<ServersConfig, _>(source.as_bytes())
+        .expect_err("config should be invalid")
+        .into_validation_errors();
+    assert_eq!(errors.problems.len(), 1);
+    assert_eq!(
+        errors.problems[0].notes,
+        ["this applies to the items [0, 2]"]
+    );
+}
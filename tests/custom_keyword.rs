@@ -0,0 +1,29 @@
+//! Tests for validating with a custom keyword, using the keyword from the `custom_keyword`
+//! example.
+
+use ts_rust_helper::style::strip_ansi;
+
+#[path = "../examples/custom_keyword.rs"]
+#[allow(dead_code)]
+mod example;
+
+#[test]
+fn custom_keyword_problems_are_located_and_normalised() {
+    let errors = example::validate_tags();
+
+    let problem = &errors.problems[0];
+    let line = problem
+        .location
+        .as_ref()
+        .and_then(|location| location.position)
+        .map(|position| position.line);
+
+    assert_eq!(problem.code(), "custom");
+    assert_eq!(problem.instance_path.as_str(), "/tags");
+    assert_eq!(line, Some(2));
+    assert_eq!(problem.notes, ["this should be the tags for the item"]);
+    assert!(
+        strip_ansi(&problem.to_string())
+            .contains("this should not contain duplicate items, ignoring case")
+    );
+}
@@ -0,0 +1,45 @@
+//! Tests for logging and reporting errors.
+
+use std::sync::mpsc;
+
+use ts_rust_helper::error::{ErrorLogger, ErrorLoggerExt};
+
+#[test]
+fn log_error_to_sends_the_error() {
+    let (tx, rx) = mpsc::channel();
+
+    let _ = Err::<(), _>("failed to read the config").log_error_to(&tx);
+    let _ = Ok::<_, &str>(()).log_error_to(&tx);
+    let _ = None::<()>.log_error_to(&tx);
+
+    drop(tx);
+    assert_eq!(
+        rx.iter().collect::<Vec<_>>(),
+        ["failed to read the config", "value was None"]
+    );
+}
+
+#[test]
+fn log_error_to_has_a_default() {
+    struct Outcome;
+    impl ErrorLogger for Outcome {
+        fn log_error(self) -> Self {
+            self
+        }
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let _ = Outcome.log_error_to(&tx);
+
+    drop(tx);
+    assert_eq!(rx.iter().count(), 0);
+}
+
+#[test]
+fn log_error_unless_skips_expected_errors() {
+    let result = Err::<(), _>("not found").log_error_unless(|error| *error == "not found");
+    assert_eq!(result, Err("not found"));
+
+    let option = None::<()>.log_error_unless(|()| true);
+    assert_eq!(option, None);
+}
@@ -0,0 +1,428 @@
+//! Tests for validating JSON and rendering the problems.
+
+use serde_json::{Value, json};
+use ts_rust_helper::{
+    json::{
+        self, DiagnosticChars, LintReport, PositionedJsonNode, ProblemStyle, Severity,
+        ValidationErrors, ValidationProblem,
+    },
+    style::strip_ansi,
+};
+
+/// A style without colours or wrapping, so rendered problems are stable.
+fn plain_style() -> ProblemStyle {
+    ProblemStyle {
+        headline: "",
+        warning: "",
+        caret: "",
+        gutter: "",
+        note: "",
+        chars: DiagnosticChars::default(),
+        max_source_width: Some(100),
+        max_note_width: None,
+    }
+}
+
+/// Validate the source against the schema, expecting it to be invalid, and render the first
+/// problem without styling.
+fn render(schema: &Value, source: &str) -> String {
+    let instance: Value = serde_json::from_str(source).expect("source should be JSON");
+    let document = PositionedJsonNode::try_parse(source);
+    let errors = json::validate(
+        schema,
+        &instance,
+        jsonschema::options(),
+        document.as_ref(),
+        Some("config.json".into()),
+    )
+    .expect_err("source should be invalid");
+
+    strip_ansi(&errors.problems[0].display_with(&plain_style()).to_string())
+}
+
+/// Validate the instance against the schema, expecting it to be invalid.
+fn validate(schema: &Value, instance: &Value) -> ValidationErrors {
+    json::validate(schema, instance, jsonschema::options(), None, None)
+        .expect_err("instance should be invalid")
+}
+
+fn errors(problems: Vec<ValidationProblem>) -> ValidationErrors {
+    let mut errors = validate(&json!({ "type": "integer" }), &json!("text"));
+    errors.problems = problems;
+    errors
+}
+
+#[test]
+fn problems_with_different_severities_are_not_equal() {
+    let error = ValidationProblem::from_message("is invalid", Some("config.json".into()));
+    let mut warning = ValidationProblem::from_message("is invalid", Some("config.json".into()));
+    warning.severity = Severity::Warning;
+
+    assert_ne!(error, warning);
+
+    let mut errors = errors(vec![error, warning]);
+    errors.dedupe();
+    assert_eq!(errors.problems.len(), 2);
+}
+
+#[test]
+fn problems_in_different_files_are_not_equal() {
+    let first = ValidationProblem::from_message("is invalid", Some("first.json".into()));
+    let second = ValidationProblem::from_message("is invalid", Some("second.json".into()));
+
+    assert_ne!(first, second);
+
+    let mut errors = errors(vec![first, second]);
+    errors.dedupe();
+    assert_eq!(errors.problems.len(), 2);
+}
+
+#[test]
+fn dedupe_removes_equal_problems() {
+    let first = ValidationProblem::from_message("is invalid", Some("config.json".into()));
+    let second = ValidationProblem::from_message("is invalid", Some("config.json".into()));
+
+    assert_eq!(first, second);
+
+    let mut errors = errors(vec![first, second]);
+    errors.dedupe();
+    assert_eq!(errors.problems.len(), 1);
+}
+
+#[test]
+fn validate_at_reports_absolute_instance_paths() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "server": { "$ref": "#/$defs/server" },
+            "name": { "type": "string" }
+        },
+        "$defs": {
+            "server": {
+                "type": "object",
+                "properties": { "port": { "type": "integer" } }
+            }
+        }
+    });
+    let instance = json!({ "server": { "port": "80" }, "name": 1 });
+
+    let problems = json::validate_at(
+        &schema,
+        &instance,
+        "/server",
+        jsonschema::options(),
+        None,
+        None,
+    )
+    .expect("subschema should create a validator");
+
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].instance_path.as_str(), "/server/port");
+}
+
+#[test]
+fn validate_at_returns_an_error_for_an_invalid_subschema() {
+    let schema = json!({
+        "properties": { "server": { "type": "not-a-type" } }
+    });
+    let instance = json!({ "server": {} });
+
+    let result = json::validate_at(
+        &schema,
+        &instance,
+        "/server",
+        jsonschema::options(),
+        None,
+        None,
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn dependent_required_problems_name_the_trigger() {
+    let schema = json!({
+        "type": "object",
+        "dependentRequired": { "useTls": ["certPath"] }
+    });
+
+    let errors = validate(&schema, &json!({ "useTls": true }));
+
+    assert_eq!(
+        errors.problems[0].message().as_deref(),
+        Some("because `useTls` is set, `certPath` is required")
+    );
+    assert!(errors.problems[0].notes.is_empty());
+}
+
+#[test]
+fn dependent_schemas_problems_note_the_trigger() {
+    let schema = json!({
+        "type": "object",
+        "dependentSchemas": {
+            "useTls": { "properties": { "port": { "const": 443 } } }
+        }
+    });
+
+    let errors = validate(&schema, &json!({ "useTls": true, "port": 80 }));
+
+    assert_eq!(
+        errors.problems[0].notes,
+        ["this applies because `useTls` is set"]
+    );
+}
+
+#[test]
+fn properties_named_like_dependency_keywords_are_not_triggers() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "dependencies": { "type": "array" },
+            "dependentRequired": {
+                "type": "object",
+                "properties": { "x": { "type": "string" } }
+            }
+        }
+    });
+
+    let errors = validate(
+        &schema,
+        &json!({ "dependencies": 1, "dependentRequired": { "x": 1 } }),
+    );
+
+    assert_eq!(errors.problems.len(), 2);
+    for problem in &errors.problems {
+        assert!(problem.notes.iter().all(|note| !note.contains("because")));
+        assert!(
+            !problem
+                .message()
+                .is_some_and(|message| message.contains("because"))
+        );
+    }
+}
+
+#[test]
+fn renders_two_digit_line_numbers_aligned() {
+    let schema = json!({
+        "type": "object",
+        "properties": { "port": { "type": "integer" } }
+    });
+    let source = format!("{{{}\n  \"port\": \"80\"\n}}", "\n".repeat(9));
+
+    assert_eq!(
+        render(&schema, &source),
+        "error[type]: `port` is the wrong type\n\
+        \x20 --> config.json:11:11\n\
+        \x20  | \n\
+        11 | \"port\": \"80\"\n\
+        \x20  |         ^^^^ this should be an integer\n"
+    );
+}
+
+#[test]
+fn html_uses_the_style_characters() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "port": { "description": "The port to listen on.", "type": "integer" }
+        }
+    });
+    let source = r#"{ "port": "80" }"#;
+    let instance: Value = serde_json::from_str(source).unwrap();
+    let document = PositionedJsonNode::try_parse(source);
+    let errors = json::validate(
+        &schema,
+        &instance,
+        jsonschema::options(),
+        document.as_ref(),
+        Some("config.json".into()),
+    )
+    .expect_err("source should be invalid");
+
+    let style = ProblemStyle {
+        chars: DiagnosticChars {
+            arrow: "->",
+            gutter: "!",
+            note: "*",
+            caret: "~",
+        },
+        ..plain_style()
+    };
+    let html = errors.to_html_with(&style);
+
+    assert!(html.contains(r#"<span class="gutter">-&gt; </span>"#));
+    assert!(html.contains(r#"<span class="gutter">1 ! </span>"#));
+    assert!(html.contains(r#"<span class="caret">~~~~</span>"#));
+    assert!(html.contains(r#"<span class="gutter"> * </span><span class="note">note:</span>"#));
+    assert!(!html.contains('^'));
+}
+
+#[test]
+fn notes_use_the_description_of_a_referenced_definition() {
+    let schema = json!({
+        "type": "object",
+        "properties": { "port": { "$ref": "#/$defs/port" } },
+        "$defs": {
+            "port": { "description": "The port to listen on.", "type": "integer" }
+        }
+    });
+
+    let errors = validate(&schema, &json!({ "port": "80" }));
+
+    assert_eq!(
+        errors.problems[0].notes,
+        ["this should be the port to listen on"]
+    );
+}
+
+/// Render a problem whose source is a long line of non-ASCII characters with `TARGET` after the
+/// prefix, returning the underlined text.
+fn underlined(prefix: usize) -> String {
+    let mut source = "é".repeat(prefix);
+    let start = source.len();
+    source.push_str("TARGET");
+    let end = source.len();
+    source.push_str(&"é".repeat(100 - prefix));
+
+    let mut problem = ValidationProblem::from_message("is invalid", None);
+    problem.source = source;
+    problem.range = start..end;
+
+    let style = ProblemStyle {
+        max_source_width: Some(40),
+        ..plain_style()
+    };
+    let rendered = strip_ansi(&problem.display_with(&style).to_string());
+    let lines: Vec<&str> = rendered.lines().collect();
+    let (source, underline) = (lines[2], lines[3]);
+
+    let underline: Vec<char> = underline.chars().collect();
+    source
+        .chars()
+        .zip(underline)
+        .filter(|(_, marker)| *marker == '^')
+        .map(|(character, _)| character)
+        .collect()
+}
+
+#[test]
+fn long_non_ascii_sources_underline_the_range() {
+    assert_eq!(underlined(0), "TARGET");
+    assert_eq!(underlined(50), "TARGET");
+    assert_eq!(underlined(100), "TARGET");
+}
+
+#[test]
+fn long_non_ascii_properties_render() {
+    let schema = json!({
+        "type": "object",
+        "additionalProperties": { "type": "integer" }
+    });
+    let source = format!(r#"{{ "{}": "{}" }}"#, "é".repeat(200), "é".repeat(60));
+
+    let rendered = render(&schema, &source);
+
+    assert!(rendered.contains("this should be an integer"));
+}
+
+/// The message of the problem from validating the instance against the schema.
+fn message(schema: &Value, instance: &Value) -> Option<String> {
+    validate(schema, instance).problems[0].message()
+}
+
+#[test]
+fn const_messages_use_the_json_representation() {
+    assert_eq!(
+        message(&json!({ "const": "on" }), &json!("off")).as_deref(),
+        Some(r#"this should be "on""#)
+    );
+    assert_eq!(
+        message(&json!({ "const": 1.5 }), &json!(2)).as_deref(),
+        Some("this should be 1.5")
+    );
+    assert_eq!(
+        message(&json!({ "const": null }), &json!(0)).as_deref(),
+        Some("this should be null")
+    );
+}
+
+#[test]
+fn enum_messages_use_the_json_representation() {
+    assert_eq!(
+        message(&json!({ "enum": ["on", 1, true, null] }), &json!("off")).as_deref(),
+        Some(r#"this should be one of ["on", 1, true, null]"#)
+    );
+}
+
+#[test]
+fn empty_ranges_render_one_caret() {
+    let problem = ValidationProblem::from_message("could not be read", Some("config.json".into()));
+    assert!(problem.range.is_empty());
+
+    let rendered = strip_ansi(&problem.display_with(&plain_style()).to_string());
+
+    assert_eq!(rendered.matches('^').count(), 1);
+}
+
+#[test]
+fn lint_reports_summarise_passed_and_failed_files() {
+    let mut warning = ValidationProblem::from_message("is unused", Some("b.json".into()));
+    warning.severity = Severity::Warning;
+    let files = vec![
+        errors(vec![]),
+        errors(vec![warning]),
+        errors(vec![ValidationProblem::from_message(
+            "is invalid",
+            Some("c.json".into()),
+        )]),
+    ];
+
+    let report = LintReport::new(files);
+    let rendered = strip_ansi(&report.to_string());
+    assert_eq!(
+        rendered.lines().last(),
+        Some("checked 3 files, 2 passed, 1 failed, 1 error, 1 warning")
+    );
+
+    let report = report.strict();
+    assert_eq!(report.failed(), 2);
+    assert_eq!(report.passed(), 1);
+
+    let report = LintReport::new(vec![errors(vec![])]);
+    let rendered = strip_ansi(&report.to_string());
+    assert_eq!(
+        rendered.lines().last(),
+        Some("checked 1 file, 1 passed, 0 failed")
+    );
+}
+
+#[test]
+fn group_by_index_merges_items_that_fail_the_same_way() {
+    let schema = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": { "port": { "type": "integer" } },
+            "required": ["name"]
+        }
+    });
+    let instance = json!([
+        { "name": "a", "port": "80" },
+        { "name": "b", "port": 443 },
+        { "name": "c", "port": "8080" },
+        { "port": 22 }
+    ]);
+
+    let mut errors = validate(&schema, &instance);
+    assert_eq!(errors.problems.len(), 3);
+    errors.group_by_index();
+
+    assert_eq!(errors.problems.len(), 2);
+    assert_eq!(errors.problems[0].instance_path.as_str(), "/0/port");
+    assert_eq!(
+        errors.problems[0].notes,
+        ["this applies to the items [0, 2]"]
+    );
+    assert_eq!(errors.problems[1].instance_path.as_str(), "/3");
+    assert!(errors.problems[1].notes.is_empty());
+}
@@ -0,0 +1,80 @@
+//! Tests for parsing JSON with positions.
+
+use proptest::prelude::*;
+use ts_rust_helper::json::PositionedJsonNode;
+
+/// The line, column, and offset of the root node of the source.
+fn root_position(source: &str) -> (usize, usize, usize) {
+    let position = PositionedJsonNode::try_parse(source).unwrap().position();
+    (position.line, position.column, position.offset)
+}
+
+#[test]
+fn object_roots_start_at_one_one() {
+    assert_eq!(root_position(r#"{ "key": 1 }"#), (1, 1, 0));
+}
+
+#[test]
+fn scalar_roots_start_at_one_one() {
+    assert_eq!(root_position("42"), (1, 1, 0));
+}
+
+/// Nest a value in arrays to the depth.
+fn nested_arrays(depth: usize) -> String {
+    format!("{}1{}", "[".repeat(depth), "]".repeat(depth))
+}
+
+#[test]
+fn documents_at_the_maximum_depth_parse() {
+    assert!(PositionedJsonNode::try_parse(&nested_arrays(128)).is_some());
+}
+
+#[test]
+fn documents_deeper_than_the_maximum_depth_do_not_parse() {
+    assert!(PositionedJsonNode::try_parse(&nested_arrays(129)).is_none());
+    assert!(PositionedJsonNode::try_parse(&nested_arrays(100_000)).is_none());
+
+    let objects = format!("{}1{}", r#"{"a":"#.repeat(100_000), "}".repeat(100_000));
+    assert!(PositionedJsonNode::try_parse(&objects).is_none());
+}
+
+proptest! {
+    #[test]
+    fn parsing_any_string_does_not_panic(source in any::<String>()) {
+        let _ = PositionedJsonNode::try_parse(&source);
+    }
+
+    #[test]
+    fn parsing_json_like_strings_does_not_panic(source in r#"[\[\]{}",:\\ \nu0-9a-fé-]{0,64}"#) {
+        let _ = PositionedJsonNode::try_parse_with_stats(&source);
+    }
+}
+
+#[test]
+fn stats_count_lines_bytes_and_depth() {
+    let source = "{\n  \"a\": [1, { \"b\": [] }],\r\n  \"é\": 2\n}\n";
+    let (node, stats) = PositionedJsonNode::try_parse_with_stats(source);
+
+    assert!(node.is_some());
+    assert_eq!(stats.lines, 4);
+    assert_eq!(stats.bytes, source.len());
+    assert_eq!(stats.max_depth, 4);
+}
+
+#[test]
+fn stats_of_an_empty_document_are_zero() {
+    let (node, stats) = PositionedJsonNode::try_parse_with_stats("");
+
+    assert!(node.is_none());
+    assert_eq!((stats.lines, stats.bytes, stats.max_depth), (0, 0, 0));
+}
+
+proptest! {
+    #[test]
+    fn stats_match_the_source(source in r#"[\[\]{}",:\\ \r\nu0-9a-fé-]{0,64}"#) {
+        let (_, stats) = PositionedJsonNode::try_parse_with_stats(&source);
+
+        prop_assert_eq!(stats.lines, source.lines().count());
+        prop_assert_eq!(stats.bytes, source.len());
+    }
+}
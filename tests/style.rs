@@ -0,0 +1,23 @@
+//! Tests for styling output.
+
+use ts_rust_helper::style::normalize_error_preserve_acronyms;
+
+#[test]
+fn normalize_error_preserve_acronyms_keeps_a_leading_acronym() {
+    assert_eq!(
+        normalize_error_preserve_acronyms("HTTP request failed."),
+        "HTTP request failed"
+    );
+    assert_eq!(
+        normalize_error_preserve_acronyms("URL is invalid."),
+        "URL is invalid"
+    );
+}
+
+#[test]
+fn normalize_error_preserve_acronyms_lowercases_a_sentence() {
+    assert_eq!(
+        normalize_error_preserve_acronyms("The file could not be read!"),
+        "the file could not be read"
+    );
+}